@@ -0,0 +1,337 @@
+//! Day 1: Chronal Calibration.
+
+extern crate aoc18_common;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::io::{self, Write};
+use std::num::ParseIntError;
+
+use aoc18_common::{Generator, ParseError, Rng, Solution};
+
+/// Reads frequency changes, which may be separated by newlines, commas or
+/// other whitespace, as in both the input and the puzzle's `+1, -2, +3, +1`.
+/// Blank lines are skipped. A change that is not a number is reported with
+/// its line.
+pub fn parse_changes(text: &str) -> Result<Vec<i64>, ParseError<ParseIntError>> {
+    let mut changes = Vec::new();
+    for (ix, line) in text.lines().enumerate() {
+        let tokens = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty());
+        for token in tokens {
+            changes.push(token.parse().map_err(|reason| ParseError::at(ix, token, reason))?);
+        }
+    }
+    Ok(changes)
+}
+
+/// The frequency after applying every change once, starting from 0, or
+/// `None` if it does not fit in an `i64`.
+pub fn part1(changes: &[i64]) -> Option<i64> {
+    changes
+        .iter()
+        .try_fold(0i64, |frequency, &change| frequency.checked_add(change))
+}
+
+/// A frequency reached for the second time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Repeat {
+    pub frequency: i64,
+    /// The pass through the list of changes that reached it, from 1.
+    pub pass: u64,
+    /// The change in the list that reached it, from 0.
+    pub index: usize,
+}
+
+/// A run of repeats, `step` changes after the start: the frequency visited
+/// second in this step, and then in every pass after it for `remaining`
+/// passes, or for as long as the frequency fits if that is `None`.
+type Event = (u128, i64, Option<u64>);
+
+/// Every frequency that is reached twice while repeating the changes, in the
+/// order that happens. Made by `repeats`.
+pub struct Repeats {
+    len: usize,
+    total: i64,
+    events: BinaryHeap<Reverse<Event>>,
+}
+
+/// The repeated frequencies of `changes`, which never end if the frequency
+/// drifts and may not even begin, as with `+1, +1`. If the first pass leaves
+/// the range of `i64`, only the repeats before that are found.
+///
+/// Rather than simulating, this looks at the frequencies of the first pass,
+/// `p`. Pass `k` visits `p + k * total`, so with a nonzero total only
+/// frequencies with the same residue modulo the total ever meet, and each
+/// value is first visited by its nearest neighbour ahead in the direction of
+/// the drift and then by the one behind that. Setting this up takes
+/// O(n log n) time, and each repeat after that O(log n).
+pub fn repeats(changes: &[i64]) -> Repeats {
+    let len = changes.len();
+    let mut firsts: Vec<i64> = Vec::with_capacity(len);
+    let mut frequency: i64 = 0;
+    let mut overflows = false;
+    for &change in changes {
+        firsts.push(frequency);
+        match frequency.checked_add(change) {
+            Some(next) => frequency = next,
+            None => {
+                overflows = true;
+                break;
+            }
+        }
+    }
+    let total = if overflows { 0 } else { frequency };
+    let mut events = BinaryHeap::new();
+
+    if overflows || total == 0 {
+        // Every pass is the same, so a frequency is reached again either
+        // later in the first pass or in the second one. If the first pass
+        // overflows, there is no second one.
+        let mut visits: HashMap<i64, Vec<usize>> = HashMap::new();
+        for (ix, &frequency) in firsts.iter().enumerate() {
+            visits.entry(frequency).or_default().push(ix);
+        }
+        for (frequency, ixs) in visits {
+            let step = match ixs.get(1) {
+                Some(&ix) => ix,
+                None if overflows => continue,
+                None => len + ixs[0],
+            };
+            events.push(Reverse((step as u128, frequency, Some(1))));
+        }
+    } else {
+        // How far a frequency is along the drift, in whole passes when two
+        // frequencies with the same residue are compared.
+        let along = |frequency: i64| i128::from(frequency) * i128::from(total.signum());
+        let per_pass = u128::from(total.unsigned_abs());
+        let mut by_residue: HashMap<i64, Vec<(i128, usize)>> = HashMap::new();
+        for (ix, &frequency) in firsts.iter().enumerate() {
+            by_residue
+                .entry(frequency.rem_euclid(total))
+                .or_default()
+                .push((-along(frequency), ix));
+        }
+        for group in by_residue.values_mut() {
+            // Furthest ahead first, and the earlier of two equal ones first.
+            group.sort();
+            for (a, pair) in group.windows(2).enumerate() {
+                let ((first, _), (second, second_ix)) = (pair[0], pair[1]);
+                // The values first visited by `first` end where the one
+                // ahead of it takes over.
+                let remaining = match a {
+                    0 => None,
+                    _ => Some(((group[a - 1].0 - first).unsigned_abs() / per_pass) as u64),
+                };
+                if remaining == Some(0) {
+                    continue;
+                }
+                let passes = (first - second).unsigned_abs() / per_pass;
+                let step = passes * len as u128 + second_ix as u128;
+                events.push(Reverse((step, firsts[group[a].1], remaining)));
+            }
+        }
+    }
+    Repeats { len, total, events }
+}
+
+impl Iterator for Repeats {
+    type Item = Repeat;
+
+    fn next(&mut self) -> Option<Repeat> {
+        let Reverse((step, frequency, remaining)) = self.events.pop()?;
+        let next_frequency = frequency.checked_add(self.total);
+        match (remaining, next_frequency) {
+            (Some(1), _) | (_, None) => {}
+            (remaining, Some(next_frequency)) => {
+                let remaining = remaining.map(|remaining| remaining - 1);
+                let next = (step + self.len as u128, next_frequency, remaining);
+                self.events.push(Reverse(next));
+            }
+        }
+        let change = step - 1;
+        Some(Repeat {
+            frequency,
+            pass: (change / self.len as u128) as u64 + 1,
+            index: (change % self.len as u128) as usize,
+        })
+    }
+}
+
+/// The first frequency reached twice while repeating the changes, or `None`
+/// if the frequency drifts away for good.
+pub fn part2(changes: &[i64]) -> Option<i64> {
+    repeats(changes).next().map(|repeat| repeat.frequency)
+}
+
+/// Writes the frequency after each change of the first `passes` passes as
+/// CSV, along with how many times that frequency has been reached so far.
+pub fn write_trace<W: Write>(changes: &[i64], passes: u64, mut out: W) -> io::Result<()> {
+    writeln!(out, "pass,index,change,frequency,visits")?;
+    let mut visits: HashMap<i128, u64> = HashMap::new();
+    let mut frequency = 0;
+    visits.insert(frequency, 1);
+    for pass in 1..=passes {
+        for (ix, &change) in changes.iter().enumerate() {
+            frequency += i128::from(change);
+            let count = visits.entry(frequency).or_insert(0);
+            *count += 1;
+            writeln!(out, "{},{},{:+},{},{}", pass, ix, change, frequency, count)?;
+        }
+    }
+    Ok(())
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse_changes(input)?)
+    }
+
+    fn part1(changes: &Self::Input) -> aoc18_common::Result<i64> {
+        Ok(part1(changes).ok_or("The frequency does not fit in 64 bits")?)
+    }
+
+    fn part2(changes: &Self::Input) -> aoc18_common::Result<i64> {
+        Ok(part2(changes).ok_or("No frequency is ever reached twice")?)
+    }
+}
+
+impl Generator for Day01 {
+    /// `size` changes of up to 20 either way. The total change is kept below
+    /// `size`, so some frequency is always reached twice.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let mut changes: Vec<i64> = (1..size)
+            .map(|_| {
+                let change = rng.range(1, 20);
+                if rng.chance(0.5) {
+                    -change
+                } else {
+                    change
+                }
+            })
+            .collect();
+        let half = (size / 2) as i64;
+        let total = rng.range(-half, half);
+        changes.push(total - changes.iter().sum::<i64>());
+        changes.iter().map(|change| format!("{:+}\n", change)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(text: &str) -> Vec<i64> {
+        parse_changes(text).unwrap()
+    }
+
+    #[test]
+    fn change_formats() {
+        assert_eq!(changes("+1, -2, +3, +1"), [1, -2, 3, 1]);
+        assert_eq!(changes("+1\n\n-2\r\n3 +1,\n"), [1, -2, 3, 1]);
+        assert_eq!(changes("\n"), []);
+        let err = parse_changes("+1, -2\n+3, 1O").unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (2, "1O"));
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1(&changes("+1, -2, +3, +1")), Some(3));
+        assert_eq!(part1(&changes("+1, +1, +1")), Some(3));
+        assert_eq!(part1(&changes("+1, +1, -2")), Some(0));
+        assert_eq!(part1(&changes("-1, -2, -3")), Some(-6));
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(part2(&changes("+1, -2, +3, +1")), Some(2));
+        assert_eq!(part2(&changes("+1, -1")), Some(0));
+        assert_eq!(part2(&changes("+3, +3, +4, -2, -4")), Some(10));
+        assert_eq!(part2(&changes("-6, +3, +8, +5, -6")), Some(5));
+        assert_eq!(part2(&changes("+7, +7, -2, -7, -4")), Some(14));
+    }
+
+    #[test]
+    fn part2_without_repeats() {
+        assert_eq!(part2(&changes("+1, +1")), None);
+        assert_eq!(part2(&changes("-3, +1")), None);
+        assert_eq!(part2(&[]), None);
+        assert_eq!(part2(&[i64::MAX / 2, i64::MIN / 2]), Some(0));
+        assert_eq!(part2(&[i64::MAX, 1]), None);
+        assert_eq!(part2(&[i64::MIN, -1]), None);
+        assert_eq!(part2(&[1, -1, i64::MAX, 1]), Some(0));
+        let found: Vec<_> = repeats(&[1, -1, 2, -1, i64::MAX, 1]).collect();
+        let repeat = |frequency, index| Repeat {
+            frequency,
+            pass: 1,
+            index,
+        };
+        assert_eq!(found, [repeat(0, 1), repeat(1, 3)]);
+        assert_eq!(part1(&[i64::MAX, 1]), None);
+        assert_eq!(part1(&[i64::MAX, 1, -1]), None);
+    }
+
+    /// Follows the changes for a bounded number of passes, noting every
+    /// frequency that is reached for the second time.
+    fn simulate(changes: &[i64], passes: u64) -> Vec<Repeat> {
+        let mut frequency = 0;
+        let mut visits = HashMap::new();
+        visits.insert(frequency, 1);
+        let mut repeats = Vec::new();
+        for pass in 1..=passes {
+            for (index, change) in changes.iter().enumerate() {
+                frequency += change;
+                let count = visits.entry(frequency).or_insert(0);
+                *count += 1;
+                if *count == 2 {
+                    repeats.push(Repeat { frequency, pass, index });
+                }
+            }
+        }
+        repeats
+    }
+
+    #[test]
+    fn repeats_match_simulation() {
+        let mut rng = Rng::new(1);
+        for _ in 0..500 {
+            let changes: Vec<i64> = (0..rng.range(1, 8)).map(|_| rng.range(-10, 10)).collect();
+            // The first pass spans at most 80, so every frequency that is
+            // ever repeated has been reached twice within 82 passes.
+            let simulated = simulate(&changes, 82);
+            let found: Vec<_> = repeats(&changes).take_while(|r| r.pass <= 82).collect();
+            assert_eq!(found, simulated, "{:?}", changes);
+            assert_eq!(part2(&changes), simulated.first().map(|r| r.frequency));
+        }
+    }
+
+    #[test]
+    fn trace() {
+        let mut csv = Vec::new();
+        write_trace(&changes("+1, -2, +3, +1"), 2, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "pass,index,change,frequency,visits");
+        assert_eq!(lines[6], "2,1,-2,2,2");
+        let first = repeats(&changes("+1, -2, +3, +1")).next();
+        assert_eq!(first, Some(Repeat { frequency: 2, pass: 2, index: 1 }));
+    }
+
+    #[test]
+    fn generated_input() {
+        for seed in 0..10 {
+            let changes = changes(&Day01::generate(&mut Rng::new(seed), 1000));
+            assert_eq!(changes.len(), 1000);
+            assert!(part2(&changes).is_some());
+        }
+    }
+}
@@ -1,39 +1,72 @@
-use std::collections::HashSet;
+extern crate aoc18_01;
+extern crate aoc18_common;
+
 use std::env;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn read_changes(f: File) -> Vec<i64> {
-    let mut changes = Vec::new();
-    for line in BufReader::new(f).lines() {
-        let change = line
-            .expect("Could not read line")
-            .parse()
-            .expect("Could not parse line");
-        changes.push(change);
-    }
-    changes
+use std::io::{BufWriter, Write};
+
+use aoc18_01::{parse_changes, repeats, write_trace, Day01};
+use aoc18_common::{fail, print_answers, solve, Input, OrExit, Source};
+
+const USAGE: &str = "\
+Usage: aoc18-01 [input] [--repeats N] [--trace FILE]
+
+--repeats lists the first N frequencies that are reached twice, with the
+pass and the index of the change that reached them. --trace writes the
+frequency after every change to FILE as CSV, for every pass up to the
+one with the first repeat.";
+
+struct Options {
+    input: Option<String>,
+    repeats: usize,
+    trace: Option<String>,
 }
 
-fn part2(changes: &Vec<i64>) -> i64 {
-    let mut seen: HashSet<i64> = HashSet::new();
-    let mut frequency = 0;
-    for change in changes.iter().cycle() {
-        frequency += change;
-        if !seen.insert(frequency) {
-            break;
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        input: None,
+        repeats: 0,
+        trace: None,
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--repeats" => {
+                let value = args.next().ok_or(USAGE)?;
+                options.repeats = value
+                    .parse()
+                    .map_err(|_| format!("Invalid number of repeats: {:?}", value))?;
+            }
+            "--trace" => options.trace = Some(args.next().ok_or(USAGE)?),
+            _ if options.input.is_none() => options.input = Some(arg),
+            _ => return Err(USAGE.to_string()),
         }
     }
-    frequency
+    Ok(options)
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let f = &args.get(1).expect("No input file given");
-    let f = File::open(f).expect("File not found");
-    let changes = read_changes(f);
-    let result_1: i64 = changes.iter().sum();
-    println!("Answer 1: {}", result_1);
-    let result_2 = part2(&changes);
-    println!("Answer 2: {}", result_2);
+    let options = parse_options().or_exit();
+    let input = Input::load(Source::from_arg(options.input.as_deref())).or_exit();
+    print_answers(&solve::<Day01>(input.text()).or_exit());
+    if options.repeats == 0 && options.trace.is_none() {
+        return;
+    }
+
+    let changes = parse_changes(input.text()).or_exit();
+    for repeat in repeats(&changes).take(options.repeats) {
+        println!(
+            "{} reached again in pass {} by change {}",
+            repeat.frequency, repeat.pass, repeat.index
+        );
+    }
+    if let Some(path) = options.trace {
+        // A frequency that never repeats is traced for a single pass.
+        let passes = repeats(&changes).next().map_or(1, |repeat| repeat.pass);
+        let file = File::create(&path).unwrap_or_else(|err| fail(format!("{}: {}", path, err)));
+        let mut out = BufWriter::new(file);
+        write_trace(&changes, passes, &mut out)
+            .and_then(|_| out.flush())
+            .or_exit();
+    }
 }
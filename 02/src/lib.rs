@@ -0,0 +1,285 @@
+//! Day 2: Inventory Management System.
+
+extern crate aoc18_common;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error;
+use std::fmt;
+
+use aoc18_common::{Generator, ParseError, Rng, Solution};
+
+/// Which characters box IDs may consist of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    /// `a` to `z`, as in the puzzle.
+    Lowercase,
+    /// Letters of any script and case.
+    Letters,
+    /// Anything but whitespace and control characters.
+    Printable,
+}
+
+impl Alphabet {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            Alphabet::Lowercase => c.is_ascii_lowercase(),
+            Alphabet::Letters => c.is_alphabetic(),
+            Alphabet::Printable => !c.is_whitespace() && !c.is_control(),
+        }
+    }
+}
+
+/// A character of a box ID that is not in the alphabet, at the given
+/// character position.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidChar {
+    pub c: char,
+    pub position: usize,
+}
+
+impl fmt::Display for InvalidChar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid character {:?} at position {}", self.c, self.position + 1)
+    }
+}
+
+impl error::Error for InvalidChar {}
+
+/// Checks that a box ID only uses characters of `alphabet`.
+pub fn check_id(boxid: &str, alphabet: Alphabet) -> Result<(), InvalidChar> {
+    match boxid.chars().enumerate().find(|&(_, c)| !alphabet.contains(c)) {
+        Some((position, c)) => Err(InvalidChar { c, position }),
+        None => Ok(()),
+    }
+}
+
+/// How often each character occurs in a box ID.
+pub fn count_chars(boxid: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for c in boxid.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// For each of a set of counts, how many IDs have some character occurring
+/// exactly that many times, and the product of those tallies.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Checksum {
+    pub tallies: BTreeMap<usize, u64>,
+    pub product: u64,
+}
+
+/// The checksum of `ids` over `counts`. The puzzle's is over 2 and 3.
+pub fn checksum(ids: &[String], counts: &[usize]) -> Checksum {
+    let mut tallies: BTreeMap<usize, u64> = counts.iter().map(|&count| (count, 0)).collect();
+    for id in ids {
+        let occurring: HashSet<usize> = count_chars(id).values().cloned().collect();
+        for (count, tally) in tallies.iter_mut() {
+            if occurring.contains(count) {
+                *tally += 1;
+            }
+        }
+    }
+    let product = tallies.values().product();
+    Checksum { tallies, product }
+}
+
+/// The checksum: the number of IDs with a letter occurring exactly twice
+/// times the number with a letter occurring exactly three times.
+pub fn part1(lines: &[String]) -> u64 {
+    checksum(lines, &[2, 3]).product
+}
+
+/// The number of positions at which two IDs differ.
+pub fn chars_diff(first: &str, second: &str) -> usize {
+    first
+        .chars()
+        .zip(second.chars())
+        .filter(|(a, b)| a != b)
+        .count()
+}
+
+/// The letters two IDs have in common at the same positions.
+pub fn common_chars(first: &str, second: &str) -> String {
+    first
+        .chars()
+        .zip(second.chars())
+        .filter(|(a, b)| a == b)
+        .map(|(a, _)| a)
+        .collect()
+}
+
+/// Splits `len` positions into `n` blocks that are as even as possible.
+fn blocks(len: usize, n: usize) -> Vec<(usize, usize)> {
+    (0..n).map(|b| (b * len / n, (b + 1) * len / n)).collect()
+}
+
+/// Every pair of IDs, as indices into `ids` with the smaller first and in
+/// increasing order, that have the same length and differ at exactly `k`
+/// positions.
+///
+/// Rather than comparing all pairs, this splits the IDs into `k + 1` blocks
+/// of positions. Two IDs that differ at only `k` positions must agree on at
+/// least one whole block, so only IDs that share a block are compared, which
+/// takes about linear time unless most IDs are alike.
+pub fn pairs_at_distance(ids: &[String], k: usize) -> Vec<(usize, usize)> {
+    let ids: Vec<Vec<char>> = ids.iter().map(|id| id.chars().collect()).collect();
+    let mut by_len: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (ix, id) in ids.iter().enumerate() {
+        by_len.entry(id.len()).or_default().push(ix);
+    }
+
+    let mut pairs = Vec::new();
+    for (len, ixs) in by_len {
+        if k > len {
+            continue;
+        }
+        let blocks = blocks(len, k + 1);
+        for (block, &(start, end)) in blocks.iter().enumerate() {
+            let mut buckets: HashMap<&[char], Vec<usize>> = HashMap::new();
+            for &ix in &ixs {
+                buckets.entry(&ids[ix][start..end]).or_default().push(ix);
+            }
+            for bucket in buckets.values() {
+                for (n, &ix_a) in bucket.iter().enumerate() {
+                    for &ix_b in &bucket[n + 1..] {
+                        let (a, b) = (&ids[ix_a], &ids[ix_b]);
+                        // Pairs that share an earlier block are found there.
+                        let found = blocks[..block]
+                            .iter()
+                            .any(|&(start, end)| a[start..end] == b[start..end]);
+                        let diff = a.iter().zip(b.iter()).filter(|(a, b)| a != b).count();
+                        if !found && diff == k {
+                            pairs.push((ix_a, ix_b));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+/// The common letters of the two IDs that differ at exactly one position.
+pub fn part2(lines: &[String]) -> Result<String, &'static str> {
+    match pairs_at_distance(lines, 1).first() {
+        Some(&(a, b)) => Ok(common_chars(&lines[a], &lines[b])),
+        None => Err("Could not find matching IDs"),
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<String>;
+    type Part1 = u64;
+    type Part2 = String;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        let mut ids = Vec::new();
+        for (ix, line) in input.lines().enumerate() {
+            check_id(line, Alphabet::Letters).map_err(|err| ParseError::at(ix, line, err))?;
+            ids.push(line.to_string());
+        }
+        Ok(ids)
+    }
+
+    fn part1(lines: &Self::Input) -> aoc18_common::Result<u64> {
+        Ok(part1(lines))
+    }
+
+    fn part2(lines: &Self::Input) -> aoc18_common::Result<String> {
+        Ok(part2(lines)?)
+    }
+}
+
+fn random_id(rng: &mut Rng) -> Vec<u8> {
+    (0..26).map(|_| b'a' + rng.below(26) as u8).collect()
+}
+
+impl Generator for Day02 {
+    /// `size` random box IDs of 26 letters, two of which differ in exactly
+    /// one position.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut ids: Vec<Vec<u8>> = (1..size.max(2)).map(|_| random_id(rng)).collect();
+        let mut twin = rng.choose(&ids).clone();
+        let ix = rng.below(twin.len());
+        let letter = (twin[ix] - b'a') as usize + 1 + rng.below(25);
+        twin[ix] = b'a' + (letter % 26) as u8;
+        ids.push(twin);
+        rng.shuffle(&mut ids);
+        ids.iter()
+            .map(|id| String::from_utf8_lossy(id) + "\n")
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let ids = Day02::parse("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab").unwrap();
+        assert_eq!(part1(&ids), 12);
+        let sum = checksum(&ids, &[4, 3, 2, 3]);
+        let tallies: Vec<_> = sum.tallies.into_iter().collect();
+        assert_eq!(tallies, [(2, 4), (3, 3), (4, 0)]);
+        assert_eq!(sum.product, 0);
+        assert_eq!(checksum(&ids, &[1]).product, 6);
+    }
+
+    #[test]
+    fn any_letters() {
+        let counts = count_chars("hähä");
+        assert_eq!((counts[&'h'], counts[&'ä']), (2, 2));
+        assert_eq!(part1(&Day02::parse("ÅÅbc\nαββγγγ").unwrap()), 2);
+        assert_eq!(
+            check_id("ab-c", Alphabet::Letters),
+            Err(InvalidChar { c: '-', position: 2 })
+        );
+        assert!(check_id("ab-c", Alphabet::Printable).is_ok());
+        assert!(check_id("äb", Alphabet::Lowercase).is_err());
+        let err = Day02::parse("abc\nab1").unwrap_err();
+        assert!(err.to_string().starts_with("line 2: Invalid character '1' at position 3"));
+    }
+
+    #[test]
+    fn part2_example() {
+        let ids = Day02::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz").unwrap();
+        assert_eq!(part2(&ids), Ok("fgij".to_string()));
+    }
+
+    #[test]
+    fn pairs_match_brute_force() {
+        let mut rng = Rng::new(2);
+        let ids: Vec<String> = (0..300)
+            .map(|_| {
+                let len = rng.range(3, 6) as usize;
+                (0..len).map(|_| *rng.choose(&['a', 'b', 'c', 'é'])).collect()
+            })
+            .collect();
+        for k in 0..5 {
+            let mut expected = Vec::new();
+            for a in 0..ids.len() {
+                for b in a + 1..ids.len() {
+                    let same_len = ids[a].chars().count() == ids[b].chars().count();
+                    if same_len && chars_diff(&ids[a], &ids[b]) == k {
+                        expected.push((a, b));
+                    }
+                }
+            }
+            assert!(!expected.is_empty());
+            assert_eq!(pairs_at_distance(&ids, k), expected, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn generated_input() {
+        let ids = Day02::parse(&Day02::generate(&mut Rng::new(1), 500)).unwrap();
+        assert_eq!(ids.len(), 500);
+        assert_eq!(part2(&ids).map(|common| common.len()), Ok(25));
+    }
+}
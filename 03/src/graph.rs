@@ -0,0 +1,100 @@
+use std::cmp;
+use std::collections::{BTreeMap, BTreeSet};
+
+use {check_claims, Claim, FabricError};
+
+/// Which claims overlap which, by claim number.
+pub struct OverlapGraph {
+    /// Every overlapping pair, the smaller number first, with the number of
+    /// square inches the two share.
+    pub edges: BTreeMap<(i32, i32), u64>,
+    neighbours: BTreeMap<i32, BTreeSet<i32>>,
+}
+
+/// The square inches two claims share.
+fn shared_area(a: &Claim, b: &Claim) -> u64 {
+    let span = |a0: i32, a1: i32, b0: i32, b1: i32| {
+        let (a0, a1, b0, b1) = (i64::from(a0), i64::from(a1), i64::from(b0), i64::from(b1));
+        cmp::max(0, cmp::min(a0 + a1, b0 + b1) - cmp::max(a0, b0)) as u64
+    };
+    span(a.x, a.w, b.x, b.w) * span(a.y, a.h, b.y, b.h)
+}
+
+impl OverlapGraph {
+    /// Finds the overlapping pairs by going through the claims from left to
+    /// right and only comparing each with the ones that start before it ends,
+    /// which takes time in proportion to the claims and the pairs found when
+    /// few claims span much of the fabric.
+    pub fn new(claims: &[Claim]) -> Result<OverlapGraph, FabricError> {
+        check_claims(claims)?;
+        let mut by_x: Vec<&Claim> = claims.iter().collect();
+        by_x.sort_by_key(|c| c.x);
+        let mut edges = BTreeMap::new();
+        let mut neighbours: BTreeMap<i32, BTreeSet<i32>> =
+            claims.iter().map(|c| (c.num, BTreeSet::new())).collect();
+        for (ix, a) in by_x.iter().enumerate() {
+            let end = i64::from(a.x) + i64::from(a.w);
+            for b in by_x[ix + 1..].iter().take_while(|b| i64::from(b.x) < end) {
+                let area = shared_area(a, b);
+                if area == 0 || a.num == b.num {
+                    continue;
+                }
+                edges.insert((cmp::min(a.num, b.num), cmp::max(a.num, b.num)), area);
+                neighbours.get_mut(&a.num).unwrap().insert(b.num);
+                neighbours.get_mut(&b.num).unwrap().insert(a.num);
+            }
+        }
+        Ok(OverlapGraph { edges, neighbours })
+    }
+
+    /// The claims that overlap claim `num`.
+    pub fn neighbours(&self, num: i32) -> Option<&BTreeSet<i32>> {
+        self.neighbours.get(&num)
+    }
+
+    /// The claims that overlap no other.
+    pub fn free(&self) -> Vec<i32> {
+        self.neighbours
+            .iter()
+            .filter(|(_, neighbours)| neighbours.is_empty())
+            .map(|(&num, _)| num)
+            .collect()
+    }
+
+    /// The connected groups of claims that overlap at least one other,
+    /// each in increasing order, ordered by their smallest claim.
+    pub fn groups(&self) -> Vec<Vec<i32>> {
+        let mut grouped = BTreeSet::new();
+        let mut groups = Vec::new();
+        for (&num, neighbours) in &self.neighbours {
+            if neighbours.is_empty() || !grouped.insert(num) {
+                continue;
+            }
+            let mut group = vec![num];
+            let mut todo = vec![num];
+            while let Some(num) = todo.pop() {
+                for &other in &self.neighbours[&num] {
+                    if grouped.insert(other) {
+                        group.push(other);
+                        todo.push(other);
+                    }
+                }
+            }
+            group.sort();
+            groups.push(group);
+        }
+        groups
+    }
+
+    /// The claims that overlap the most others, and how many that is.
+    pub fn most_conflicting(&self) -> (usize, Vec<i32>) {
+        let most = self.neighbours.values().map(|n| n.len()).max().unwrap_or(0);
+        let claims = self
+            .neighbours
+            .iter()
+            .filter(|(_, neighbours)| most > 0 && neighbours.len() == most)
+            .map(|(&num, _)| num)
+            .collect();
+        (most, claims)
+    }
+}
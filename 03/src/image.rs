@@ -0,0 +1,63 @@
+use std::io::{self, Write};
+
+use {Claim, Fabric};
+
+/// How to write a fabric as an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// A greyscale PGM, brighter where more claims overlap.
+    Pgm,
+    /// A colour PPM, from dark blue for unclaimed fabric through green for
+    /// a single claim to yellow, orange and red for more.
+    Ppm,
+}
+
+const HEAT: [[u8; 3]; 5] = [
+    [16, 16, 48],
+    [40, 160, 60],
+    [230, 210, 40],
+    [240, 120, 30],
+    [220, 30, 30],
+];
+
+const OUTLINE: [u8; 3] = [255, 255, 255];
+
+impl Fabric {
+    /// Writes one pixel per square inch, as a binary PGM or PPM. If a claim
+    /// is given, its edge is drawn in white on top.
+    pub fn write_image<W: Write>(
+        &self,
+        format: ImageFormat,
+        outline: Option<&Claim>,
+        mut out: W,
+    ) -> io::Result<()> {
+        let magic = match format {
+            ImageFormat::Pgm => "P5",
+            ImageFormat::Ppm => "P6",
+        };
+        write!(out, "{}\n{} {}\n255\n", magic, self.width, self.height)?;
+        let mut pixels = Vec::with_capacity(self.width * self.height * 3);
+        for dy in 0..self.height {
+            for dx in 0..self.width {
+                let (x, y) = (self.left + dx as i64, self.top + dy as i64);
+                let edge = outline.is_some_and(|claim| on_edge(claim, x, y));
+                let heat = (self.count(x, y) as usize).min(HEAT.len() - 1);
+                match format {
+                    ImageFormat::Pgm if edge => pixels.push(255),
+                    ImageFormat::Pgm => pixels.push((heat * 200 / (HEAT.len() - 1)) as u8),
+                    ImageFormat::Ppm if edge => pixels.extend_from_slice(&OUTLINE),
+                    ImageFormat::Ppm => pixels.extend_from_slice(&HEAT[heat]),
+                }
+            }
+        }
+        out.write_all(&pixels)
+    }
+}
+
+/// Whether the square inch at `x`, `y` is on the border of a claim.
+fn on_edge(claim: &Claim, x: i64, y: i64) -> bool {
+    let (left, top) = (i64::from(claim.x), i64::from(claim.y));
+    let (right, bottom) = (left + i64::from(claim.w) - 1, top + i64::from(claim.h) - 1);
+    let inside = left <= x && x <= right && top <= y && y <= bottom;
+    inside && (x == left || x == right || y == top || y == bottom)
+}
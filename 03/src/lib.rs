@@ -0,0 +1,378 @@
+//! Day 3: No Matter How You Slice It.
+
+extern crate aoc18_common;
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+mod graph;
+mod image;
+mod sweep;
+
+use std::error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use aoc18_common::{parse_records, Generator, Rng, Solution};
+use regex::Regex;
+
+pub use graph::OverlapGraph;
+pub use image::ImageFormat;
+pub use sweep::{sweep, Overlaps};
+
+/// Why a line could not be parsed as a claim.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Format,
+    Number(ParseIntError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Format => write!(f, "Expected a claim like \"#1 @ 1,3: 4x4\""),
+            Error::Number(err) => write!(f, "Bad number ({})", err),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Number(err)
+    }
+}
+
+/// A rectangle of fabric claimed by an elf, `w` by `h` inches with its top
+/// left corner `x` inches from the left edge and `y` from the top.
+pub struct Claim {
+    pub num: i32,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl FromStr for Claim {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref claim_regex: Regex =
+                Regex::new(r"#(?P<num>\d+) @ (?P<x>-?\d+),(?P<y>-?\d+): (?P<w>-?\d+)x(?P<h>-?\d+)")
+                    .unwrap();
+        }
+        let captures = claim_regex.captures(s).ok_or(Error::Format)?;
+        let num_fromstr = captures["num"].parse::<i32>()?;
+        let x_fromstr = captures["x"].parse::<i32>()?;
+        let y_fromstr = captures["y"].parse::<i32>()?;
+        let w_fromstr = captures["w"].parse::<i32>()?;
+        let h_fromstr = captures["h"].parse::<i32>()?;
+        Ok(Claim {
+            num: num_fromstr,
+            x: x_fromstr,
+            y: y_fromstr,
+            w: w_fromstr,
+            h: h_fromstr,
+        })
+    }
+}
+
+impl fmt::Display for Claim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{} @ {},{}: {}x{}", self.num, self.x, self.y, self.w, self.h)
+    }
+}
+
+/// The largest fabric, in square inches, that `get_cloth` will allocate.
+/// The puzzle's is a million.
+pub const MAX_AREA: u64 = 1 << 28;
+
+/// Why claims could not be laid out on a fabric.
+#[derive(Debug, PartialEq)]
+pub enum FabricError {
+    /// The claim with this number has a negative position or size.
+    Negative(i32),
+    /// The claims span a `width` by `height` area larger than `MAX_AREA`.
+    TooLarge { width: u64, height: u64 },
+}
+
+impl fmt::Display for FabricError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FabricError::Negative(num) => {
+                write!(f, "Claim #{} has a negative position or size", num)
+            }
+            FabricError::TooLarge { width, height } => write!(
+                f,
+                "The claims span {} by {} inches, more than {} square inches",
+                width, height, MAX_AREA
+            ),
+        }
+    }
+}
+
+impl error::Error for FabricError {}
+
+/// The number of claims covering each square inch of the smallest rectangle
+/// that contains all claims. Counts stop at `u16::MAX`.
+pub struct Fabric {
+    pub left: i64,
+    pub top: i64,
+    pub width: usize,
+    pub height: usize,
+    counts: Vec<u16>,
+}
+
+impl Fabric {
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        let (dx, dy) = (x - self.left, y - self.top);
+        if dx < 0 || dy < 0 || dx >= self.width as i64 || dy >= self.height as i64 {
+            return None;
+        }
+        Some(dy as usize * self.width + dx as usize)
+    }
+
+    /// The number of claims covering the square inch at `x`, `y`.
+    pub fn count(&self, x: i64, y: i64) -> u16 {
+        self.index(x, y).map_or(0, |ix| self.counts[ix])
+    }
+
+    /// The counts of every square inch, row by row.
+    pub fn counts(&self) -> &[u16] {
+        &self.counts
+    }
+}
+
+/// Every square inch of a claim.
+fn squares(claim: &Claim) -> impl Iterator<Item = (i64, i64)> {
+    let (x, y) = (i64::from(claim.x), i64::from(claim.y));
+    let (w, h) = (i64::from(claim.w), i64::from(claim.h));
+    (y..y + h).flat_map(move |y| (x..x + w).map(move |x| (x, y)))
+}
+
+fn check_claims(claims: &[Claim]) -> Result<(), FabricError> {
+    match claims.iter().find(|c| c.x < 0 || c.y < 0 || c.w < 0 || c.h < 0) {
+        Some(claim) => Err(FabricError::Negative(claim.num)),
+        None => Ok(()),
+    }
+}
+
+/// Lays the claims out on a fabric just large enough to hold them.
+pub fn get_cloth(claims: &[Claim]) -> Result<Fabric, FabricError> {
+    check_claims(claims)?;
+    let left = claims.iter().map(|c| i64::from(c.x)).min().unwrap_or(0);
+    let top = claims.iter().map(|c| i64::from(c.y)).min().unwrap_or(0);
+    let right = claims.iter().map(|c| i64::from(c.x) + i64::from(c.w)).max().unwrap_or(0);
+    let bottom = claims.iter().map(|c| i64::from(c.y) + i64::from(c.h)).max().unwrap_or(0);
+    let (width, height) = ((right - left) as u64, (bottom - top) as u64);
+    if width * height > MAX_AREA {
+        return Err(FabricError::TooLarge { width, height });
+    }
+
+    let mut fabric = Fabric {
+        left,
+        top,
+        width: width as usize,
+        height: height as usize,
+        counts: vec![0; (width * height) as usize],
+    };
+    for claim in claims {
+        for (x, y) in squares(claim) {
+            let ix = fabric.index(x, y).unwrap();
+            fabric.counts[ix] = fabric.counts[ix].saturating_add(1);
+        }
+    }
+    Ok(fabric)
+}
+
+/// The number of square inches within two or more claims, counted one by
+/// one. `sweep` finds it without looking at each square inch.
+pub fn part1(cloth: &Fabric) -> usize {
+    cloth.counts().iter().filter(|&&count| count > 1).count()
+}
+
+/// The first claim that does not overlap any other.
+pub fn part2(claims: &[Claim], cloth: &Fabric) -> Option<i32> {
+    claims
+        .iter()
+        .find(|claim| squares(claim).all(|(x, y)| cloth.count(x, y) == 1))
+        .map(|claim| claim.num)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Claim>;
+    type Part1 = u64;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse_records(input)?)
+    }
+
+    fn part1(claims: &Self::Input) -> aoc18_common::Result<u64> {
+        Ok(sweep(claims)?.area)
+    }
+
+    fn part2(claims: &Self::Input) -> aoc18_common::Result<i32> {
+        let free = sweep(claims)?.free;
+        Ok(*free.first().ok_or("No claim is free of overlaps")?)
+    }
+}
+
+fn random_claim(rng: &mut Rng, num: i32) -> Claim {
+    let w = rng.range(1, 29) as i32;
+    let h = rng.range(1, 29) as i32;
+    Claim {
+        num,
+        x: rng.range(0, (1000 - w).into()) as i32,
+        y: rng.range(0, (1000 - h).into()) as i32,
+        w,
+        h,
+    }
+}
+
+fn overlaps(a: &Claim, b: &Claim) -> bool {
+    a.x < b.x + b.w && b.x < a.x + a.w && a.y < b.y + b.h && b.y < a.y + a.h
+}
+
+impl Generator for Day03 {
+    /// `size` claims of up to 29 by 29 inches on the 1000 by 1000 fabric.
+    /// One claim, somewhere in the list, overlaps no other.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1) as i32;
+        let free_num = rng.range(1, size.into()) as i32;
+        let free = random_claim(rng, free_num);
+        let mut text = String::new();
+        for num in 1..=size {
+            if num == free.num {
+                text += &format!("{}\n", free);
+                continue;
+            }
+            let claim = loop {
+                let claim = random_claim(rng, num);
+                if !overlaps(&claim, &free) {
+                    break claim;
+                }
+            };
+            text += &format!("{}\n", claim);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::slice;
+
+    const EXAMPLE: &str = "\
+#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2";
+
+    #[test]
+    fn parse_claim() {
+        let claim: Claim = "#123 @ 3,2: 5x4".parse().unwrap();
+        assert_eq!((claim.num, claim.x, claim.y, claim.w, claim.h), (123, 3, 2, 5, 4));
+        assert_eq!("#1 @ 1,3 4x4".parse::<Claim>().err(), Some(Error::Format));
+    }
+
+    #[test]
+    fn example() {
+        let claims = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part1(&claims).unwrap(), 4);
+        assert_eq!(Day03::part2(&claims).unwrap(), 3);
+        let cloth = get_cloth(&claims).unwrap();
+        assert_eq!((cloth.left, cloth.top, cloth.width, cloth.height), (1, 1, 6, 6));
+        assert_eq!((cloth.count(4, 4), cloth.count(0, 0)), (2, 0));
+    }
+
+    #[test]
+    fn fabric_errors() {
+        let claims = Day03::parse("#1 @ 1,3: 4x4\n#2 @ 3,-1: 4x4").unwrap();
+        assert_eq!(get_cloth(&claims).err(), Some(FabricError::Negative(2)));
+        let claims = Day03::parse("#1 @ 1,3: 4x4\n#2 @ 2000000000,2000000000: 4x4").unwrap();
+        assert!(matches!(get_cloth(&claims), Err(FabricError::TooLarge { .. })));
+        let claims = Day03::parse("#1 @ 1000,1000: 1x1\n#2 @ 1500,1002: 2x2").unwrap();
+        assert_eq!(Day03::part2(&claims).unwrap(), 1);
+    }
+
+    #[test]
+    fn generated_input() {
+        let input = Day03::generate(&mut Rng::new(1), 1000);
+        let claims = Day03::parse(&input).unwrap();
+        assert_eq!(claims.len(), 1000);
+        Day03::part1(&claims).unwrap();
+        Day03::part2(&claims).unwrap();
+    }
+
+    #[test]
+    fn overlap_graph() {
+        let input = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n#4 @ 6,6: 3x1\n#5 @ 9,1: 1x1";
+        let graph = OverlapGraph::new(&Day03::parse(input).unwrap()).unwrap();
+        let edges: Vec<_> = graph.edges.iter().map(|(&pair, &area)| (pair, area)).collect();
+        assert_eq!(edges, [((1, 2), 4), ((3, 4), 1)]);
+        assert_eq!(graph.groups(), [vec![1, 2], vec![3, 4]]);
+        assert_eq!(graph.most_conflicting(), (1, vec![1, 2, 3, 4]));
+        assert_eq!(graph.free(), [5]);
+        assert_eq!(graph.neighbours(4).unwrap().iter().collect::<Vec<_>>(), [&3]);
+
+        let input = Day03::generate(&mut Rng::new(4), 2000);
+        let claims = Day03::parse(&input).unwrap();
+        let mut free = sweep(&claims).unwrap().free;
+        free.sort();
+        assert_eq!(OverlapGraph::new(&claims).unwrap().free(), free);
+    }
+
+    #[test]
+    fn images() {
+        let claims = Day03::parse(EXAMPLE).unwrap();
+        let cloth = get_cloth(&claims).unwrap();
+        let mut pgm = Vec::new();
+        cloth.write_image(ImageFormat::Pgm, None, &mut pgm).unwrap();
+        assert!(pgm.starts_with(b"P5\n6 6\n255\n"));
+        assert_eq!(pgm.len(), 11 + 36);
+        // Square (4, 4) is claimed twice, and at (3, 3) in the image.
+        assert_eq!(pgm[11 + 3 * 6 + 3], 100);
+        let mut ppm = Vec::new();
+        cloth.write_image(ImageFormat::Ppm, Some(&claims[2]), &mut ppm).unwrap();
+        assert_eq!(ppm.len(), 11 + 3 * 36);
+        let pixel = |x: usize, y: usize| &ppm[11 + 3 * (y * 6 + x)..][..3];
+        assert_eq!(pixel(4, 4), [255, 255, 255]);
+        assert_eq!(pixel(3, 3), [230, 210, 40]);
+        assert_eq!(pixel(0, 0), [16, 16, 48]);
+    }
+
+    #[test]
+    fn sweep_matches_fabric() {
+        let mut rng = Rng::new(3);
+        for _ in 0..200 {
+            let claims: Vec<_> = (1..rng.range(1, 40) as i32)
+                .map(|num| Claim {
+                    num,
+                    x: rng.range(0, 30) as i32,
+                    y: rng.range(0, 30) as i32,
+                    w: rng.range(0, 12) as i32,
+                    h: rng.range(0, 12) as i32,
+                })
+                .collect();
+            let cloth = get_cloth(&claims).unwrap();
+            let free: Vec<_> = claims
+                .iter()
+                .filter(|c| part2(slice::from_ref(*c), &cloth).is_some())
+                .map(|c| c.num)
+                .collect();
+            let overlaps = sweep(&claims).unwrap();
+            assert_eq!(overlaps.area, part1(&cloth) as u64);
+            assert_eq!(overlaps.free, free);
+        }
+        let huge = Day03::parse("#1 @ 0,0: 2000000000x2000000000\n#2 @ 5,5: 1000000x2").unwrap();
+        let overlaps = sweep(&huge).unwrap();
+        assert_eq!(overlaps, Overlaps { area: 2000000, free: vec![] });
+    }
+}
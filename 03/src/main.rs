@@ -1,102 +1,78 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+extern crate aoc18_03;
+extern crate aoc18_common;
 
 use std::env;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::num::ParseIntError;
-use std::str::FromStr;
-use std::vec::Vec;
+use std::io::{BufWriter, Write};
 
-use regex::Regex;
+use aoc18_03::{get_cloth, Day03, ImageFormat};
+use aoc18_common::{fail, print_answers, solve, Input, OrExit, Solution, Source};
 
-struct Claim {
-    num: i32,
-    x: i32,
-    y: i32,
-    w: i32,
-    h: i32,
-}
+const USAGE: &str = "\
+Usage: aoc18-03 [input] [--image FILE] [--outline CLAIM]
 
-impl FromStr for Claim {
-    type Err = ParseIntError;
+--image draws the fabric to FILE with one pixel per square inch, coloured by
+how many claims cover it. FILE is a greyscale PGM if it ends in .pgm and a
+colour PPM otherwise. --outline draws the edge of claim number CLAIM.";
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref claim_regex: Regex =
-                Regex::new(r"#(?P<num>\d+) @ (?P<x>\d+),(?P<y>\d+): (?P<w>\d+)x(?P<h>\d+)")
-                    .unwrap();
-        }
-        let captures = claim_regex.captures(s).expect("Could not parse line");
-        let num_fromstr = captures["num"].parse::<i32>()?;
-        let x_fromstr = captures["x"].parse::<i32>()?;
-        let y_fromstr = captures["y"].parse::<i32>()?;
-        let w_fromstr = captures["w"].parse::<i32>()?;
-        let h_fromstr = captures["h"].parse::<i32>()?;
-        Ok(Claim {
-            num: num_fromstr,
-            x: x_fromstr,
-            y: y_fromstr,
-            w: w_fromstr,
-            h: h_fromstr,
-        })
-    }
-}
-
-fn get_cloth(claims: &Vec<Claim>) -> [[u16; 1000]; 1000] {
-    let mut cloth = [[0u16; 1000]; 1000];
-    for claim in claims {
-        for i in 0..claim.w {
-            let x_coord = (claim.x + i) as usize;
-            for j in 0..claim.h {
-                let y_coord = (claim.y + j) as usize;
-                cloth[x_coord][y_coord] += 1;
-            }
-        }
-    }
-    cloth
+struct Options {
+    input: Option<String>,
+    image: Option<String>,
+    outline: Option<i32>,
 }
 
-fn part1(cloth: &[[u16; 1000]; 1000]) -> i32 {
-    let mut overlaps = 0;
-    for row in cloth.iter() {
-        for cell in row.iter() {
-            if *cell > 1 {
-                overlaps += 1;
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        input: None,
+        image: None,
+        outline: None,
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--image" => options.image = Some(args.next().ok_or(USAGE)?),
+            "--outline" => {
+                let value = args.next().ok_or(USAGE)?;
+                let num = value
+                    .trim_start_matches('#')
+                    .parse()
+                    .map_err(|_| format!("Invalid claim number: {:?}", value))?;
+                options.outline = Some(num);
             }
+            _ if options.input.is_none() => options.input = Some(arg),
+            _ => return Err(USAGE.to_string()),
         }
     }
-    overlaps
-}
-
-fn part2(claims: &Vec<Claim>, cloth: &[[u16; 1000]; 1000]) -> Option<i32> {
-    'claim_loop: for claim in claims {
-        for i in 0..claim.w {
-            let x_coord = (claim.x + i) as usize;
-            for j in 0..claim.h {
-                let y_coord = (claim.y + j) as usize;
-                if cloth[x_coord][y_coord] != 1 {
-                    continue 'claim_loop;
-                }
-            }
-        }
-        return Some(claim.num);
+    if options.outline.is_some() && options.image.is_none() {
+        return Err("--outline needs an --image to draw on".to_string());
     }
-    None
+    Ok(options)
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let f = &args.get(1).expect("No input file given");
-    let f = File::open(f).expect("File not found");
-    let lines: Result<Vec<_>, _> = BufReader::new(f).lines().collect();
-    let lines = lines.expect("Could not read lines from file");
-    let claims: Result<Vec<Claim>, _> = lines.iter().map(|s| s.parse::<Claim>()).collect();
-    let claims = claims.expect("Could not parse claims");
-    let cloth = get_cloth(&claims);
-    let result_1 = part1(&cloth);
-    println!("Answer: {}", result_1);
-    let result_2 = part2(&claims, &cloth).expect("No answer for part 2?!");
-    println!("Answer: {}", result_2);
+    let options = parse_options().or_exit();
+    let input = Input::load(Source::from_arg(options.input.as_deref())).or_exit();
+    print_answers(&solve::<Day03>(input.text()).or_exit());
+
+    if let Some(path) = options.image {
+        let claims = Day03::parse(input.text()).or_exit();
+        let outline = options.outline.map(|num| {
+            claims
+                .iter()
+                .find(|claim| claim.num == num)
+                .unwrap_or_else(|| fail(format!("There is no claim #{}", num)))
+        });
+        let format = if path.ends_with(".pgm") {
+            ImageFormat::Pgm
+        } else {
+            ImageFormat::Ppm
+        };
+        let cloth = get_cloth(&claims).or_exit();
+        let file = File::create(&path).unwrap_or_else(|err| fail(format!("{}: {}", path, err)));
+        let mut out = BufWriter::new(file);
+        cloth
+            .write_image(format, outline, &mut out)
+            .and_then(|_| out.flush())
+            .or_exit();
+    }
 }
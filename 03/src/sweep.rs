@@ -0,0 +1,200 @@
+use std::cmp;
+
+use {check_claims, Claim, FabricError};
+
+/// What a sweep over the claims finds: the square inches within two or more
+/// claims and the numbers of the claims that overlap no other, in input
+/// order.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Overlaps {
+    pub area: u64,
+    pub free: Vec<i32>,
+}
+
+/// A segment tree over the elementary intervals between the claims' `y`
+/// edges. Coverage is never pushed down: `cover` counts the claims covering
+/// all of a node, and the other fields describe the node's subtree on top of
+/// what its ancestors cover.
+struct Tree {
+    edges: Vec<i64>,
+    cover: Vec<u32>,
+    /// How much of the node is covered at least once and at least twice.
+    once: Vec<i64>,
+    twice: Vec<i64>,
+    /// The most claims covering any one point of the node.
+    most: Vec<u32>,
+    /// The latest insertion into any part of the node, and into all of it.
+    stamp: Vec<usize>,
+    stamp_all: Vec<usize>,
+}
+
+impl Tree {
+    fn new(edges: Vec<i64>) -> Tree {
+        let nodes = 4 * edges.len();
+        Tree {
+            edges,
+            cover: vec![0; nodes],
+            once: vec![0; nodes],
+            twice: vec![0; nodes],
+            most: vec![0; nodes],
+            stamp: vec![0; nodes],
+            stamp_all: vec![0; nodes],
+        }
+    }
+
+    fn root(&self) -> (usize, usize, usize) {
+        (1, 0, self.edges.len() - 1)
+    }
+
+    fn pull(&mut self, node: usize, lo: usize, hi: usize) {
+        let full = self.edges[hi] - self.edges[lo];
+        let leaf = hi - lo == 1;
+        let (left, right) = (2 * node, 2 * node + 1);
+        let child = |field: &Vec<i64>| if leaf { 0 } else { field[left] + field[right] };
+        let (once, twice) = (child(&self.once), child(&self.twice));
+        self.once[node] = if self.cover[node] > 0 { full } else { once };
+        self.twice[node] = match self.cover[node] {
+            0 => twice,
+            1 => once,
+            _ => full,
+        };
+        let most = if leaf {
+            0
+        } else {
+            cmp::max(self.most[left], self.most[right])
+        };
+        self.most[node] = self.cover[node] + most;
+    }
+
+    /// Adds `delta` claims covering `range` in edge indices, stamping the
+    /// range with `stamp` if given.
+    fn update(
+        &mut self,
+        (node, lo, hi): (usize, usize, usize),
+        range: (usize, usize),
+        delta: i32,
+        stamp: Option<usize>,
+    ) {
+        if range.1 <= lo || hi <= range.0 {
+            return;
+        }
+        if let Some(stamp) = stamp {
+            self.stamp[node] = cmp::max(self.stamp[node], stamp);
+        }
+        if range.0 <= lo && hi <= range.1 {
+            self.cover[node] = (self.cover[node] as i32 + delta) as u32;
+            if let Some(stamp) = stamp {
+                self.stamp_all[node] = cmp::max(self.stamp_all[node], stamp);
+            }
+        } else {
+            let mid = (lo + hi) / 2;
+            self.update((2 * node, lo, mid), range, delta, stamp);
+            self.update((2 * node + 1, mid, hi), range, delta, stamp);
+        }
+        self.pull(node, lo, hi);
+    }
+
+    /// The most claims covering any point of `range`, and the latest stamp
+    /// on any part of it.
+    fn query(&self, (node, lo, hi): (usize, usize, usize), range: (usize, usize)) -> (u32, usize) {
+        if range.1 <= lo || hi <= range.0 {
+            return (0, 0);
+        }
+        if range.0 <= lo && hi <= range.1 {
+            return (self.most[node], self.stamp[node]);
+        }
+        let mid = (lo + hi) / 2;
+        let left = self.query((2 * node, lo, mid), range);
+        let right = self.query((2 * node + 1, mid, hi), range);
+        (
+            self.cover[node] + cmp::max(left.0, right.0),
+            cmp::max(self.stamp_all[node], cmp::max(left.1, right.1)),
+        )
+    }
+}
+
+/// Finds the overlaps between claims by sweeping a line across the fabric
+/// from left to right, in O(n log n) time for n claims however large they
+/// are.
+///
+/// The tree holds the claims the line currently crosses. When a claim
+/// starts, it overlaps an earlier one if the tree already covers part of
+/// its `y` range. When it ends, it overlaps a later one if any claim was
+/// inserted into its `y` range after it was.
+pub fn sweep(claims: &[Claim]) -> Result<Overlaps, FabricError> {
+    check_claims(claims)?;
+    let spans: Vec<_> = claims
+        .iter()
+        .map(|c| {
+            let (x, y) = (i64::from(c.x), i64::from(c.y));
+            (x, x + i64::from(c.w), y, y + i64::from(c.h))
+        })
+        .collect();
+    let mut edges: Vec<i64> = spans
+        .iter()
+        .filter(|&&(x0, x1, y0, y1)| x0 < x1 && y0 < y1)
+        .flat_map(|&(_, _, y0, y1)| vec![y0, y1])
+        .collect();
+    edges.sort();
+    edges.dedup();
+    let mut overlapping = vec![false; claims.len()];
+    if edges.is_empty() {
+        return Ok(Overlaps {
+            area: 0,
+            free: claims.iter().map(|c| c.num).collect(),
+        });
+    }
+
+    // Claims end before others start at the same `x`, since they only
+    // touch. The event kind sorts ends first.
+    let mut events = Vec::new();
+    for (ix, &(x0, x1, y0, y1)) in spans.iter().enumerate() {
+        if x0 < x1 && y0 < y1 {
+            events.push((x0, 1, ix));
+            events.push((x1, 0, ix));
+        }
+    }
+    events.sort();
+
+    let edge = |y: i64| edges.binary_search(&y).unwrap();
+    let ranges: Vec<_> = spans
+        .iter()
+        .map(|&(x0, x1, y0, y1)| {
+            if x0 < x1 && y0 < y1 {
+                (edge(y0), edge(y1))
+            } else {
+                (0, 0)
+            }
+        })
+        .collect();
+    let mut tree = Tree::new(edges.clone());
+    let mut inserted = vec![0; claims.len()];
+    let mut area = 0;
+    let mut last_x = events[0].0;
+    for (n, &(x, kind, ix)) in events.iter().enumerate() {
+        area += (x - last_x) as u64 * tree.twice[1] as u64;
+        last_x = x;
+        let range = ranges[ix];
+        if kind == 1 {
+            // Stamps start at 1 so that 0 means never.
+            inserted[ix] = n + 1;
+            if tree.query(tree.root(), range).0 > 0 {
+                overlapping[ix] = true;
+            }
+            tree.update(tree.root(), range, 1, Some(n + 1));
+        } else {
+            if tree.query(tree.root(), range).1 > inserted[ix] {
+                overlapping[ix] = true;
+            }
+            tree.update(tree.root(), range, -1, None);
+        }
+    }
+
+    let free = claims
+        .iter()
+        .zip(overlapping)
+        .filter(|&(_, overlapping)| !overlapping)
+        .map(|(c, _)| c.num)
+        .collect();
+    Ok(Overlaps { area, free })
+}
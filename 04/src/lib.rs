@@ -0,0 +1,496 @@
+//! Day 4: Repose Record.
+
+extern crate aoc18_common;
+
+mod stats;
+mod time;
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use aoc18_common::{parse_records, Generator, ParseError, Rng, Solution};
+
+pub use stats::SleepStats;
+pub use time::{Date, Timestamp};
+use time::is_leap_year;
+
+/// Why a line could not be parsed as a guard log event.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Format,
+    Number(ParseIntError),
+    /// A field of the timestamp, such as the minute, is out of range.
+    Range(&'static str, u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Format => write!(
+                f,
+                "Expected an event like \"[1518-11-01 00:00] Guard #10 begins shift\""
+            ),
+            Error::Number(err) => write!(f, "Bad number ({})", err),
+            Error::Range(field, value) => write!(f, "The {} {} is out of range", field, value),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Number(err)
+    }
+}
+
+/// What happened in a line of the guard log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EventKind {
+    GuardBegins(u32),
+    FallsAsleep,
+    WakesUp,
+}
+
+/// A line of the guard log. Events sort by their timestamps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Event {
+    pub time: Timestamp,
+    pub kind: EventKind,
+}
+
+impl FromStr for Event {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s.strip_prefix('[').ok_or(Error::Format)?;
+        let end = rest.find(']').ok_or(Error::Format)?;
+        let time = rest[..end].parse()?;
+        let kind = match rest[end + 1..].trim() {
+            "falls asleep" => EventKind::FallsAsleep,
+            "wakes up" => EventKind::WakesUp,
+            text => {
+                let guard_no = text
+                    .strip_prefix("Guard #")
+                    .and_then(|text| text.strip_suffix(" begins shift"))
+                    .ok_or(Error::Format)?;
+                if guard_no.is_empty() || !guard_no.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(Error::Format);
+                }
+                EventKind::GuardBegins(guard_no.parse()?)
+            }
+        };
+        Ok(Event { time, kind })
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            EventKind::GuardBegins(guard_no) => {
+                write!(f, "[{}] Guard #{} begins shift", self.time, guard_no)
+            }
+            EventKind::FallsAsleep => write!(f, "[{}] falls asleep", self.time),
+            EventKind::WakesUp => write!(f, "[{}] wakes up", self.time),
+        }
+    }
+}
+
+/// One guard's night on duty. Only the midnight hour counts, so naps are
+/// given as minutes past midnight, from falling asleep up to but not
+/// including waking up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shift {
+    pub guard: u32,
+    /// The night the shift covers, named by the date at midnight. A guard
+    /// who starts at 23:58 is on duty for the next date.
+    pub night: Date,
+    pub naps: Vec<(u8, u8)>,
+}
+
+/// The minute of the midnight hour of `night` at `time`, with earlier times
+/// at 0 and later ones at 60.
+fn minute_of_night(time: Timestamp, night: Date) -> u8 {
+    if time.date < night {
+        0
+    } else if time.date == night && time.hour == 0 {
+        time.minute
+    } else {
+        60
+    }
+}
+
+/// An event of the log along with its line number, from 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Entry {
+    pub event: Event,
+    pub line: usize,
+}
+
+/// Reads a log and puts its events in chronological order.
+pub fn read_log(text: &str) -> Result<Vec<Entry>, ParseError<Error>> {
+    let events: Vec<Event> = parse_records(text)?;
+    let mut entries: Vec<_> = (1..)
+        .zip(events)
+        .map(|(line, event)| Entry { event, line })
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// An event that makes no sense where it is in the log.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LogError {
+    /// An event before any guard has begun a shift.
+    NoGuard,
+    /// A guard falls asleep while asleep.
+    AlreadyAsleep,
+    /// A guard wakes up without being asleep.
+    NotAsleep,
+    /// A guard falls asleep and is still asleep when the next shift
+    /// begins or the log ends.
+    NeverWakes,
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogError::NoGuard => write!(f, "No guard is on duty yet"),
+            LogError::AlreadyAsleep => write!(f, "The guard is already asleep"),
+            LogError::NotAsleep => write!(f, "The guard is not asleep"),
+            LogError::NeverWakes => write!(f, "The guard never wakes up"),
+        }
+    }
+}
+
+impl error::Error for LogError {}
+
+enum State {
+    OffDuty,
+    Awake,
+    Asleep(Entry),
+}
+
+/// Splits a log into shifts. `entries` must be in chronological order, as
+/// `read_log` gives them, and every nap must end in the shift it began in.
+pub fn shifts(entries: &[Entry]) -> Result<Vec<Shift>, ParseError<LogError>> {
+    let error = |entry: &Entry, reason| ParseError {
+        line: entry.line,
+        text: entry.event.to_string(),
+        reason,
+    };
+    let mut shifts: Vec<Shift> = Vec::new();
+    let mut state = State::OffDuty;
+    for entry in entries {
+        let time = entry.event.time;
+        state = match (entry.event.kind, state) {
+            (EventKind::GuardBegins(_), State::Asleep(since)) => {
+                return Err(error(&since, LogError::NeverWakes))
+            }
+            (EventKind::GuardBegins(guard), _) => {
+                let night = if time.hour >= 12 {
+                    time.date.next()
+                } else {
+                    time.date
+                };
+                shifts.push(Shift {
+                    guard,
+                    night,
+                    naps: Vec::new(),
+                });
+                State::Awake
+            }
+            (_, State::OffDuty) => return Err(error(entry, LogError::NoGuard)),
+            (EventKind::FallsAsleep, State::Awake) => State::Asleep(*entry),
+            (EventKind::FallsAsleep, State::Asleep(_)) => {
+                return Err(error(entry, LogError::AlreadyAsleep))
+            }
+            (EventKind::WakesUp, State::Awake) => return Err(error(entry, LogError::NotAsleep)),
+            (EventKind::WakesUp, State::Asleep(since)) => {
+                let shift = shifts.last_mut().unwrap();
+                let from = minute_of_night(since.event.time, shift.night);
+                let to = minute_of_night(time, shift.night);
+                if from < to {
+                    shift.naps.push((from, to));
+                }
+                State::Awake
+            }
+        };
+    }
+    match state {
+        State::Asleep(since) => Err(error(&since, LogError::NeverWakes)),
+        _ => Ok(shifts),
+    }
+}
+
+/// For each guard, the number of times they were asleep at each minute
+/// past midnight.
+pub fn get_sleep_patterns(shifts: &[Shift]) -> HashMap<u32, [u32; 60]> {
+    let mut sleep_patterns: HashMap<u32, [u32; 60]> = HashMap::new();
+    for shift in shifts {
+        let pattern = sleep_patterns.entry(shift.guard).or_insert([0; 60]);
+        for &(from, to) in &shift.naps {
+            for minute in from..to {
+                pattern[minute as usize] += 1;
+            }
+        }
+    }
+    sleep_patterns
+}
+
+/// The shifts as a table like the one in the puzzle, with a row per shift
+/// marking the minutes of the midnight hour the guard is asleep with `#`.
+pub fn timeline(shifts: &[Shift]) -> String {
+    let ids: Vec<_> = shifts.iter().map(|shift| format!("#{}", shift.guard)).collect();
+    let width = ids.iter().map(|id| id.len()).max().unwrap_or(0).max(2);
+    let mut table = format!("Date   {:<width$}  Minute\n", "ID", width = width);
+    let indent = " ".repeat(7 + width + 2);
+    let tens: String = (0..60).map(|minute| (b'0' + minute / 10) as char).collect();
+    let ones: String = (0..60).map(|minute| (b'0' + minute % 10) as char).collect();
+    table += &format!("{}{}\n{}{}\n", indent, tens, indent, ones);
+    for (shift, id) in shifts.iter().zip(ids) {
+        let mut minutes = [b'.'; 60];
+        for &(from, to) in &shift.naps {
+            for minute in &mut minutes[from as usize..to as usize] {
+                *minute = b'#';
+            }
+        }
+        table += &format!(
+            "{:02}-{:02}  {:<width$}  {}\n",
+            shift.night.month,
+            shift.night.day,
+            id,
+            String::from_utf8_lossy(&minutes),
+            width = width
+        );
+    }
+    table
+}
+
+/// Strategy 1: the guard who sleeps the most times their sleepiest minute.
+pub fn part1(shifts: &[Shift]) -> Option<u32> {
+    let stats = SleepStats::new(shifts);
+    let guard = stats.sleepiest_guard()?;
+    let (minute, _) = stats.sleepiest_minute(guard)?;
+    Some(guard * u32::from(minute))
+}
+
+/// Strategy 2: the guard who is most often asleep at the same minute times
+/// that minute.
+pub fn part2(shifts: &[Shift]) -> Option<u32> {
+    let (guard, minute, _) = SleepStats::new(shifts).most_regular()?;
+    Some(guard * u32::from(minute))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Shift>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(shifts(&read_log(input)?)?)
+    }
+
+    fn part1(shifts: &Self::Input) -> aoc18_common::Result<u32> {
+        Ok(part1(shifts).ok_or("No guards in the log")?)
+    }
+
+    fn part2(shifts: &Self::Input) -> aoc18_common::Result<u32> {
+        Ok(part2(shifts).ok_or("No guards in the log")?)
+    }
+}
+
+/// The date `n_days` after 1518-01-01, as it appears in the log.
+fn date(mut n_days: usize) -> String {
+    let mut year = 1518;
+    loop {
+        let year_days = if is_leap_year(year) { 366 } else { 365 };
+        if n_days < year_days {
+            break;
+        }
+        n_days -= year_days;
+        year += 1;
+    }
+    let feb = if is_leap_year(year) { 29 } else { 28 };
+    let month_days = [31, feb, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    let mut month = 0;
+    while n_days >= month_days[month] {
+        n_days -= month_days[month];
+        month += 1;
+    }
+    format!("{}-{:02}-{:02}", year, month + 1, n_days + 1)
+}
+
+impl Generator for Day04 {
+    /// Logs of `size` shifts on consecutive nights from 1518-01-01, by
+    /// about one guard per ten shifts. A guard naps up to three times per
+    /// shift. Like in the real input, the lines are in random order.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let guards: Vec<i64> = (0..size / 10 + 1).map(|_| rng.range(1, 3499)).collect();
+        let mut lines = Vec::new();
+        for night in 0..size {
+            let guard = rng.choose(&guards);
+            let (begin, first_nap) = if rng.chance(0.5) {
+                (format!("{} 23:{:02}", date(night), rng.range(45, 59)), 0)
+            } else {
+                let mins = rng.range(0, 5);
+                (format!("{} 00:{:02}", date(night + 1), mins), mins + 1)
+            };
+            lines.push(format!("[{}] Guard #{} begins shift", begin, guard));
+            let mut minutes: Vec<i64> = (first_nap..60).collect();
+            rng.shuffle(&mut minutes);
+            minutes.truncate(2 * rng.below(4));
+            minutes.sort();
+            for nap in minutes.chunks(2) {
+                let day = date(night + 1);
+                lines.push(format!("[{} 00:{:02}] falls asleep", day, nap[0]));
+                lines.push(format!("[{} 00:{:02}] wakes up", day, nap[1]));
+            }
+        }
+        rng.shuffle(&mut lines);
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+    #[test]
+    fn example() {
+        let shifts = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&shifts), Some(240));
+        assert_eq!(part2(&shifts), Some(4455));
+    }
+
+    #[test]
+    fn example_out_of_order() {
+        let mut lines: Vec<_> = EXAMPLE.lines().collect();
+        lines.reverse();
+        let shifts = Day04::parse(&lines.join("\n")).unwrap();
+        assert_eq!(part1(&shifts), Some(240));
+        assert_eq!(part2(&shifts), Some(4455));
+    }
+
+    #[test]
+    fn example_timeline() {
+        let table = "\
+Date   ID   Minute
+            000000000011111111112222222222333333333344444444445555555555
+            012345678901234567890123456789012345678901234567890123456789
+11-01  #10  .....####################.....#########################.....
+11-02  #99  ........................................##########..........
+11-03  #10  ........................#####...............................
+11-04  #99  ....................................##########..............
+11-05  #99  .............................................##########.....
+";
+        assert_eq!(timeline(&Day04::parse(EXAMPLE).unwrap()), table);
+    }
+
+    #[test]
+    fn example_stats() {
+        let shifts = Day04::parse(EXAMPLE).unwrap();
+        let stats = SleepStats::new(&shifts);
+        assert_eq!(stats.guards().collect::<Vec<_>>(), [10, 99]);
+        assert_eq!((stats.minutes_asleep(10), stats.minutes_asleep(99)), (50, 30));
+        assert_eq!(stats.sleepiest_minute(10), Some((24, 2)));
+        // Guard 99 is asleep three times at 45 only.
+        assert_eq!(stats.sleepiest_minute(99), Some((45, 3)));
+        assert_eq!(stats.histogram(99).unwrap()[36..41], [1, 1, 1, 1, 2]);
+        assert_eq!(stats.most_regular(), Some((99, 45, 3)));
+        let night = "1518-11-02".parse().unwrap();
+        assert_eq!(stats.asleep_at(night, 45), [99]);
+        assert_eq!(stats.asleep_at(night, 50), []);
+    }
+
+    #[test]
+    fn ties_go_to_the_lowest() {
+        let log = "\
+[1518-11-01 00:00] Guard #20 begins shift
+[1518-11-01 00:10] falls asleep
+[1518-11-01 00:20] wakes up
+[1518-11-02 00:00] Guard #10 begins shift
+[1518-11-02 00:30] falls asleep
+[1518-11-02 00:40] wakes up";
+        let shifts = Day04::parse(log).unwrap();
+        let stats = SleepStats::new(&shifts);
+        assert_eq!(stats.sleepiest_guard(), Some(10));
+        assert_eq!(stats.most_regular(), Some((10, 30, 1)));
+        assert_eq!(part1(&shifts), Some(300));
+        assert_eq!(part2(&shifts), Some(300));
+    }
+
+    #[test]
+    fn log_errors() {
+        let err = |log: &str| {
+            let err = shifts(&read_log(log).unwrap()).unwrap_err();
+            (err.line, err.reason)
+        };
+        let begins = "[1518-11-01 00:00] Guard #10 begins shift\n";
+        assert_eq!(err("[1518-11-01 00:05] falls asleep"), (1, LogError::NoGuard));
+        assert_eq!(
+            err(&format!("[1518-11-01 00:25] wakes up\n{}", begins)),
+            (1, LogError::NotAsleep)
+        );
+        let twice = "[1518-11-01 00:05] falls asleep\n[1518-11-01 00:07] falls asleep";
+        assert_eq!(err(&format!("{}{}", begins, twice)), (3, LogError::AlreadyAsleep));
+        let asleep = "[1518-11-01 00:05] falls asleep\n";
+        assert_eq!(err(&format!("{}{}", begins, asleep)), (2, LogError::NeverWakes));
+        let next = "[1518-11-01 23:58] Guard #99 begins shift";
+        assert_eq!(err(&format!("{}{}{}", begins, asleep, next)), (2, LogError::NeverWakes));
+    }
+
+    #[test]
+    fn timestamps() {
+        let event: Event = "[1518-12-31 23:58] Guard #99 begins shift".parse().unwrap();
+        assert_eq!(event.to_string(), "[1518-12-31 23:58] Guard #99 begins shift");
+        let shifts = shifts(&[Entry { event, line: 1 }]).unwrap();
+        assert_eq!(shifts[0].night, "1519-01-01".parse().unwrap());
+        let err = |line: &str| line.parse::<Event>().err();
+        assert_eq!(err("[1518-02-29 00:00] wakes up"), Some(Error::Range("day", 29)));
+        assert_eq!(err("[1518-11-01 00:60] wakes up"), Some(Error::Range("minute", 60)));
+        assert_eq!(err("[1518-11-01 0:05] wakes up"), Some(Error::Format));
+        assert_eq!(err("[1518-11-01 00:05] Guard #x begins shift"), Some(Error::Format));
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(date(0), "1518-01-01");
+        assert_eq!(date(58), "1518-02-28");
+        assert_eq!(date(59), "1518-03-01");
+        assert_eq!(date(365 + 365 + 59), "1520-02-29");
+    }
+
+    #[test]
+    fn generated_input() {
+        let shifts = Day04::parse(&Day04::generate(&mut Rng::new(1), 500)).unwrap();
+        assert_eq!(shifts.len(), 500);
+        assert!(part1(&shifts).is_some());
+        assert!(part2(&shifts).is_some());
+    }
+}
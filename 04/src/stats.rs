@@ -0,0 +1,80 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use {get_sleep_patterns, Date, Shift};
+
+/// Questions about who slept when. Ties always go to the lower guard ID
+/// and the earlier minute, so the answers do not depend on hashing.
+pub struct SleepStats<'a> {
+    shifts: &'a [Shift],
+    patterns: BTreeMap<u32, [u32; 60]>,
+}
+
+impl<'a> SleepStats<'a> {
+    pub fn new(shifts: &'a [Shift]) -> SleepStats<'a> {
+        SleepStats {
+            shifts,
+            patterns: get_sleep_patterns(shifts).into_iter().collect(),
+        }
+    }
+
+    /// Every guard with at least one shift, in increasing order.
+    pub fn guards(&self) -> impl Iterator<Item = u32> + '_ {
+        self.patterns.keys().cloned()
+    }
+
+    /// How many times a guard was asleep at each minute past midnight.
+    pub fn histogram(&self, guard: u32) -> Option<&[u32; 60]> {
+        self.patterns.get(&guard)
+    }
+
+    /// The total number of minutes a guard was asleep.
+    pub fn minutes_asleep(&self, guard: u32) -> u32 {
+        self.histogram(guard)
+            .map_or(0, |pattern| pattern.iter().sum())
+    }
+
+    /// The minute a guard was most often asleep at, and how often.
+    pub fn sleepiest_minute(&self, guard: u32) -> Option<(u8, u32)> {
+        let pattern = self.histogram(guard)?;
+        (0..60u8)
+            .map(|minute| (minute, pattern[minute as usize]))
+            .max_by_key(|&(minute, count)| (count, Reverse(minute)))
+    }
+
+    /// The guard who was asleep for the most minutes in total.
+    pub fn sleepiest_guard(&self) -> Option<u32> {
+        self.guards()
+            .max_by_key(|&guard| (self.minutes_asleep(guard), Reverse(guard)))
+    }
+
+    /// The guard and minute that go together most often, and how often the
+    /// guard was asleep at that minute.
+    pub fn most_regular(&self) -> Option<(u32, u8, u32)> {
+        self.guards()
+            .filter_map(|guard| {
+                let (minute, count) = self.sleepiest_minute(guard)?;
+                Some((guard, minute, count))
+            })
+            .max_by_key(|&(guard, minute, count)| (count, Reverse(guard), Reverse(minute)))
+    }
+
+    /// The guards asleep at `minute` past midnight on the night of `night`.
+    pub fn asleep_at(&self, night: Date, minute: u8) -> Vec<u32> {
+        let mut guards: Vec<_> = self
+            .shifts
+            .iter()
+            .filter(|shift| shift.night == night)
+            .filter(|shift| {
+                shift
+                    .naps
+                    .iter()
+                    .any(|&(from, to)| from <= minute && minute < to)
+            })
+            .map(|shift| shift.guard)
+            .collect();
+        guards.sort();
+        guards.dedup();
+        guards
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use Error;
+
+pub fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parses a field of a timestamp, which must consist of digits only and lie
+/// within `range`.
+fn field<T>(s: &str, name: &'static str, range: (u32, u32)) -> Result<T, Error>
+where
+    T: FromStr<Err = ParseIntError> + Into<u32> + Copy,
+{
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::Format);
+    }
+    let value: T = s.parse()?;
+    if value.into() < range.0 || value.into() > range.1 {
+        return Err(Error::Range(name, value.into()));
+    }
+    Ok(value)
+}
+
+/// A day of the calendar, such as `1518-11-01`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// The day after this one.
+    pub fn next(self) -> Date {
+        if self.day < days_in_month(self.year, self.month) {
+            Date {
+                day: self.day + 1,
+                ..self
+            }
+        } else if self.month < 12 {
+            Date {
+                month: self.month + 1,
+                day: 1,
+                ..self
+            }
+        } else {
+            Date {
+                year: self.year + 1,
+                month: 1,
+                day: 1,
+            }
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('-').collect();
+        if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+            return Err(Error::Format);
+        }
+        let year = field(parts[0], "year", (0, 9999))?;
+        let month = field(parts[1], "month", (1, 12))?;
+        let day = field(parts[2], "day", (1, u32::from(days_in_month(year, month))))?;
+        Ok(Date { year, month, day })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A point in time of the log, to the minute, such as `1518-11-01 23:58`.
+/// Timestamps order chronologically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub date: Date,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (date, time) = s.split_at(s.find(' ').ok_or(Error::Format)?);
+        let time = &time[1..];
+        if time.len() != 5 || time.as_bytes()[2] != b':' {
+            return Err(Error::Format);
+        }
+        Ok(Timestamp {
+            date: date.parse()?,
+            hour: field(&time[..2], "hour", (0, 23))?,
+            minute: field(&time[3..], "minute", (0, 59))?,
+        })
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:02}:{:02}", self.date, self.hour, self.minute)
+    }
+}
@@ -0,0 +1,148 @@
+//! Day 5: Alchemical Reduction.
+
+extern crate aoc18_common;
+
+use aoc18_common::{Generator, Rng, Solution};
+
+/// Whether two units are of the same type but opposite polarities.
+fn reacts(a: u8, b: u8) -> bool {
+    a ^ b == b'a' ^ b'A' && a.is_ascii_alphabetic()
+}
+
+/// The polymer after all reactions. Bytes that are not ASCII letters are
+/// ignored.
+///
+/// The reduced prefix is kept on a stack. Each new unit either reacts with
+/// the unit on top, which is popped, or is pushed itself, so this takes a
+/// single pass and every unit is pushed and popped at most once.
+pub fn reduce(polymer: &[u8]) -> Vec<u8> {
+    let mut stack = Vec::with_capacity(polymer.len());
+    for &unit in polymer.iter().filter(|unit| unit.is_ascii_alphabetic()) {
+        if stack.last().is_some_and(|&top| reacts(top, unit)) {
+            stack.pop();
+        } else {
+            stack.push(unit);
+        }
+    }
+    stack
+}
+
+/// The length of the polymer after all reactions.
+pub fn part1(input: &str) -> usize {
+    reduce(input.as_bytes()).len()
+}
+
+/// The length of the fully reacted polymer after removing all units of the
+/// type `c`, which is given in lower case.
+pub fn part2_char(c: char, input: &str) -> usize {
+    let polymer: Vec<u8> = input
+        .bytes()
+        .filter(|&unit| unit.to_ascii_lowercase() as char != c)
+        .collect();
+    reduce(&polymer).len()
+}
+
+/// The shortest fully reacted polymer after removing a single unit type.
+pub fn part2(input: &str) -> usize {
+    (b'a'..=b'z')
+        .map(|c| part2_char(c as char, input))
+        .min()
+        .unwrap()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(polymer: &Self::Input) -> aoc18_common::Result<usize> {
+        Ok(part1(polymer))
+    }
+
+    fn part2(polymer: &Self::Input) -> aoc18_common::Result<usize> {
+        Ok(part2(polymer))
+    }
+}
+
+impl Generator for Day05 {
+    /// A polymer of `size` random units.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut polymer: String = (0..size)
+            .map(|_| {
+                let unit = (b'a' + rng.below(26) as u8) as char;
+                if rng.chance(0.5) {
+                    unit.to_ascii_uppercase()
+                } else {
+                    unit
+                }
+            })
+            .collect();
+        polymer.push('\n');
+        polymer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "dabAcCaCBAcCcaDA";
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1("aA"), 0);
+        assert_eq!(part1("abBA"), 0);
+        assert_eq!(part1("abAB"), 4);
+        assert_eq!(part1("aabAAB"), 6);
+        assert_eq!(part1(EXAMPLE), 10);
+    }
+
+    /// Removes the first reacting pair until there is none.
+    fn reduce_naively(polymer: &[u8]) -> Vec<u8> {
+        let mut polymer: Vec<u8> = polymer
+            .iter()
+            .cloned()
+            .filter(u8::is_ascii_alphabetic)
+            .collect();
+        while let Some(ix) = polymer.windows(2).position(|pair| reacts(pair[0], pair[1])) {
+            polymer.drain(ix..ix + 2);
+        }
+        polymer
+    }
+
+    #[test]
+    fn reduce_matches_naive_reduction() {
+        assert_eq!(reduce(EXAMPLE.as_bytes()), b"dabCBAcaDA");
+        assert_eq!(reduce(b"a@`A"), b"");
+        let mut rng = Rng::new(5);
+        for _ in 0..2000 {
+            // Few unit types, so that most polymers react a lot.
+            let polymer: Vec<u8> = (0..rng.range(0, 40))
+                .map(|_| *rng.choose(b"aAbBcC@`\n"))
+                .collect();
+            assert_eq!(reduce(&polymer), reduce_naively(&polymer), "{:?}", polymer);
+        }
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2_char('a', EXAMPLE), 6);
+        assert_eq!(part2_char('b', EXAMPLE), 8);
+        assert_eq!(part2_char('c', EXAMPLE), 4);
+        assert_eq!(part2_char('d', EXAMPLE), 6);
+        assert_eq!(part2(EXAMPLE), 4);
+    }
+
+    #[test]
+    fn generated_input() {
+        let polymer = Day05::parse(&Day05::generate(&mut Rng::new(1), 5000)).unwrap();
+        assert_eq!(polymer.len(), 5000);
+        assert!(part2(&polymer) <= part1(&polymer));
+    }
+}
@@ -0,0 +1,390 @@
+//! Day 6: Chronal Coordinates.
+
+extern crate aoc18_common;
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use aoc18_common::{parse_records, Generator, Rng, Solution};
+
+/// Why a line could not be parsed as a point.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Format,
+    Number(ParseIntError),
+    OutOfGrid,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Format => write!(f, "Expected a point like \"1, 6\""),
+            Error::Number(err) => write!(f, "Bad number ({})", err),
+            Error::OutOfGrid => write!(f, "Coordinates must be from 0 to 359"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Number(err)
+    }
+}
+
+#[derive(Hash, PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl FromStr for Point {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.splitn(2, ", ").collect();
+        if parts.len() != 2 {
+            return Err(Error::Format);
+        }
+        let x = parts[0].parse::<i32>()?;
+        let y = parts[1].parse::<i32>()?;
+        if !(0..360).contains(&x) || !(0..360).contains(&y) {
+            return Err(Error::OutOfGrid);
+        }
+        Ok(Point { x, y })
+    }
+}
+
+// At first, I thought computing the convex hull would give the set of points
+// with infinite area. But that didn't hold in taxicab geometry, it turned out.
+// So this is left only for posterity.
+#[allow(dead_code)]
+fn orientation(l_a: &Point, l_b: &Point, c: &Point) -> Ordering {
+    let res =
+        (l_b.x - l_a.x) * (c.y - l_a.y) - (c.x - l_a.x) * (l_b.y - l_a.y);
+    res.cmp(&0)
+}
+
+#[allow(dead_code)]
+fn giftwrap_next<'a>(points: &'a [Point], current: &Point) -> &'a Point {
+    points
+        .iter()
+        .filter(|p| p != &current)
+        .min_by(|a, b| orientation(current, a, b))
+        .unwrap()
+}
+
+#[allow(dead_code)]
+fn giftwrap(points: &[Point]) -> HashSet<&Point> {
+    let start = points.iter().min_by_key(|p| p.x).unwrap();
+    let mut convex_hull: HashSet<&Point> = HashSet::new();
+    convex_hull.insert(start);
+    let mut current = start;
+    loop {
+        let next = giftwrap_next(points, current);
+        if next == start {
+            break;
+        } else {
+            assert!(convex_hull.insert(next));
+            current = next;
+        }
+    }
+    convex_hull
+}
+
+#[derive(Copy, Clone, Debug)]
+enum AreaState<'a> {
+    Nothing,
+    Contested,
+    Preliminary(&'a Point),
+    Owned(&'a Point),
+}
+
+fn silly_hash(point: &Point) -> char {
+    ((((point.x * 397 + point.y) % 26) + ('A' as i32) - 1) as u8) as char
+}
+
+impl<'a> fmt::Display for AreaState<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AreaState::*;
+        let disp = match self {
+            Nothing => '.',
+            Contested => '#',
+            Owned(p) => silly_hash(p),
+            _ => 'ö',
+        };
+        write!(f, "{}", disp)
+    }
+}
+
+fn take<'a>(area: &AreaState, point: &'a Point) -> Option<AreaState<'a>> {
+    use AreaState::*;
+    match area {
+        Nothing => Some(Preliminary(point)),
+        Preliminary(p) if p == &point => None,
+        Preliminary(_) => Some(Contested),
+        _ => None,
+    }
+}
+
+fn manhattan_step_new<'a>(
+    area: &mut [[AreaState<'a>; 360]; 360],
+    points: &[(&'a Point, Point)],
+) -> Vec<(&'a Point, Point)> {
+    let mut neighbours: Vec<(&Point, Point)> = Vec::new();
+    for (owner, p) in points {
+        if p.x < 0 || p.x >= 360 || p.y < 0 || p.y >= 360 {
+            continue;
+        }
+
+        let state = &mut area[p.x as usize][p.y as usize];
+        let before = &mut AreaState::Nothing;
+        std::mem::swap(state, before);
+        let new_state = take(before, owner);
+        match new_state {
+            Some(inner) => {
+                *state = inner;
+                for adjacent in adjacent_points(p).iter() {
+                    neighbours.push((owner, *adjacent));
+                }
+            }
+            None => {
+                *state = *before;
+            }
+        }
+    }
+    neighbours
+}
+
+fn adjacent_points(p: &Point) -> [Point; 4] {
+    [Point { x: p.x - 1, y: p.y },
+     Point { x: p.x + 1, y: p.y },
+     Point { x: p.x, y: p.y - 1 },
+     Point { x: p.x, y: p.y + 1 }]
+}
+
+fn lock<'a>(area: &AreaState<'a>) -> Option<AreaState<'a>> {
+    use AreaState::*;
+    match area {
+        Preliminary(p) => Some(Owned(p)),
+        _ => None,
+    }
+}
+
+fn lock_step<'a>(area: &mut [[AreaState<'a>; 360]; 360]) {
+    for row in area.iter_mut() {
+        for elem in row.iter_mut() {
+            if let Some(inner) = lock(elem) {
+                *elem = inner;
+            }
+        }
+    }
+}
+
+fn manhattan_new<'a>(area: &mut [[AreaState<'a>; 360]; 360], points: &'a [Point]) {
+    let mut step_points: Vec<(&Point, Point)> = points.iter().map(|p| (p, *p)).collect();
+    loop {
+        step_points = manhattan_step_new(area, &step_points);
+        if step_points.is_empty() {
+            break;
+        }
+        lock_step(area);
+    }
+}
+
+fn get_owner<'a>(area: &AreaState<'a>) -> Option<&'a Point> {
+    match area {
+        AreaState::Owned(p) => Some(p),
+        _ => None,
+    }
+}
+
+fn do_thing<'a>(set: &mut HashSet<&'a Point>, area: &[[AreaState<'a>; 360]; 360], i: usize) {
+    let areas = [area[0][i], area[i][0], area[359][i], area[i][359]];
+    let vals = areas.iter().filter_map(get_owner);
+    for val in vals {
+        set.insert(val);
+    }
+}
+
+type CostsCalculator = [i32; 360];
+
+fn get_axis_costs(points: &[Point], f: &dyn Fn(&Point) -> i32) -> CostsCalculator {
+    let n_points = points.len() as i32;
+    let mut points_sorted: Vec<&Point> = points.iter().collect();
+    points_sorted.sort_by_key(|p| f(p));
+
+    let mut costs: [i32; 360] = [0; 360];
+    let mut costs_ix: usize = 0;
+    let mut points_to_left = 0;
+    let mut points_to_right = n_points;
+    let mut current_cost = points_sorted.iter().map(|p| f(p)).sum();
+    for point in points_sorted {
+        while costs_ix < (f(point) as usize) {
+            current_cost += points_to_left;
+            current_cost -= points_to_right;
+            costs[costs_ix] = current_cost;
+            costs_ix += 1;
+        }
+        points_to_left += 1;
+        points_to_right -= 1;
+    }
+    assert_eq!(points_to_right, 0);
+    assert_eq!(points_to_left, n_points);
+    for cost in costs.iter_mut().skip(costs_ix) {
+        current_cost += n_points;
+        *cost = current_cost;
+    }
+    costs
+}
+
+/// The size of the largest finite area of locations closest to one point,
+/// if any area is finite. All points must lie within a 360 by 360 grid.
+pub fn part1(points: &[Point]) -> Option<i64> {
+    let mut area: [[AreaState; 360]; 360] = [[AreaState::Nothing; 360]; 360];
+    manhattan_new(&mut area, points);
+    let mut counts: HashMap<&Point, i64> = HashMap::new();
+    //for row in area.iter() {
+    //    for elem in row.iter() {
+    //        print!("{}", elem);
+    //    }
+    //    println!("");
+    //}
+    let owners: Vec<&Point> = area
+        .iter()
+        .map(|row| row
+             .iter()
+             .filter_map(get_owner)
+             .collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+        .as_slice()
+        .concat();
+    let mut edgy: HashSet<&Point> = HashSet::new();
+    for i in 0..360 {
+        do_thing(&mut edgy, &area, i);
+    }
+    for owner in owners {
+        if edgy.contains(&owner) {
+            continue;
+        }
+        counts.entry(owner)
+            .and_modify(|c| *c += 1)
+            .or_insert(1);
+    }
+    counts.values().max().copied()
+}
+
+/// The total distance to all points that the region of part 2 stays under.
+pub const MAX_TOTAL_DISTANCE: i32 = 10000;
+
+/// The size of the region of locations whose total distance to all points
+/// is less than `max_total_distance`.
+pub fn part2(points: &[Point], max_total_distance: i32) -> i64 {
+    let x_costs = get_axis_costs(points, &|p| p.x);
+    let y_costs = get_axis_costs(points, &|p| p.y);
+    let mut count = 0;
+    for x_cost in x_costs.iter() {
+        for y_cost in y_costs.iter() {
+            if x_cost + y_cost < max_total_distance {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Point>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        let points: Vec<Point> = parse_records(input)?;
+        if points.is_empty() {
+            return Err("No points in the input".into());
+        }
+        Ok(points)
+    }
+
+    fn part1(points: &Self::Input) -> aoc18_common::Result<i64> {
+        Ok(part1(points).ok_or("Every area is infinite")?)
+    }
+
+    fn part2(points: &Self::Input) -> aoc18_common::Result<i64> {
+        Ok(part2(points, MAX_TOTAL_DISTANCE))
+    }
+}
+
+impl Generator for Day06 {
+    /// `size` distinct points, at least five, well within the 360 by 360
+    /// grid that `part1` works on. The first five form a plus, so that the
+    /// one in the middle has a finite area.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let (x, y) = (rng.range(40, 320) as i32, rng.range(40, 320) as i32);
+        let d = rng.range(5, 20) as i32;
+        let mut points = vec![
+            Point { x, y },
+            Point { x: x - d, y },
+            Point { x: x + d, y },
+            Point { x, y: y - d },
+            Point { x, y: y + d },
+        ];
+        let mut seen: HashSet<Point> = points.iter().copied().collect();
+        while points.len() < size.min(300 * 300) {
+            let point = Point {
+                x: rng.range(20, 339) as i32,
+                y: rng.range(20, 339) as i32,
+            };
+            if seen.insert(point) {
+                points.push(point);
+            }
+        }
+        points
+            .iter()
+            .map(|p| format!("{}, {}\n", p.x, p.y))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1, 1
+1, 6
+8, 3
+3, 4
+5, 5
+8, 9";
+
+    #[test]
+    fn example() {
+        let points = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&points), Some(17));
+        assert_eq!(part2(&points, 32), 16);
+    }
+
+    #[test]
+    fn generated_input() {
+        let points = Day06::parse(&Day06::generate(&mut Rng::new(1), 100)).unwrap();
+        assert_eq!(points.len(), 100);
+        assert!(part1(&points).unwrap() > 0);
+    }
+
+    #[test]
+    fn bad_input() {
+        assert!(Day06::parse("").is_err());
+        assert_eq!("400, 6".parse::<Point>(), Err(Error::OutOfGrid));
+        assert_eq!("-4, 6".parse::<Point>(), Err(Error::OutOfGrid));
+        assert!(Day06::parse("1, 1\n359, 0").is_ok());
+        assert_eq!(part1(&[Point { x: 1, y: 1 }]), None);
+    }
+}
@@ -0,0 +1,343 @@
+//! Day 7: The Sum of Its Parts.
+
+extern crate aoc18_common;
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use aoc18_common::{parse_records, Generator, Rng, Solution};
+use regex::Regex;
+
+/// A step, along with the steps that must be finished before it. Nodes are
+/// ordered so that a `BinaryHeap` pops them in alphabetical order.
+#[derive(Eq)]
+pub struct Node {
+    pub name: char,
+    pub incoming: Vec<char>,
+}
+
+impl Node {
+    /// Step A takes `base_cost + 1` seconds, B `base_cost + 2` and so on.
+    pub fn time_cost(&self, base_cost: usize) -> usize {
+        base_cost + 1 + (self.name as usize) - ('A' as usize)
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} <- {:?}", self.name, self.incoming)
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Node) -> bool {
+        self.name.eq(&other.name)
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Node) -> Ordering {
+        self.name.cmp(&other.name).reverse()
+    }
+}
+
+/// Why a line could not be parsed as an edge.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Format,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Format => write!(
+                f,
+                "Expected an edge like \"Step C must be finished before step A can begin.\""
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// An instruction that step `before` must be finished before step `after`
+/// can begin.
+#[derive(PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub before: char,
+    pub after: char,
+}
+
+impl FromStr for Edge {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref edge_regex: Regex = Regex::new(
+                "Step (?P<before>.) must be finished before step (?P<after>.) can begin."
+            )
+            .unwrap();
+        }
+        let captures = edge_regex.captures(s).ok_or(Error::Format)?;
+        let before = captures["before"].chars().next().unwrap();
+        let after = captures["after"].chars().next().unwrap();
+        Ok(Edge { before, after })
+    }
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Step {} must be finished before step {} can begin.",
+            self.before, self.after
+        )
+    }
+}
+
+/// The steps named in `edges`, by name.
+pub fn create_dag(edges: &[Edge]) -> HashMap<char, Node> {
+    let mut nodes: HashMap<char, Node> = HashMap::new();
+    for edge in edges {
+        nodes.entry(edge.before).or_insert(Node {
+            name: edge.before,
+            incoming: Vec::new(),
+        });
+        let after = nodes.entry(edge.after).or_insert(Node {
+            name: edge.after,
+            incoming: Vec::new(),
+        });
+        after.incoming.push(edge.before);
+    }
+    nodes
+}
+
+/// Scheduler for the steps of a DAG built by `create_dag`.
+pub struct TopoSort<'n> {
+    nodes: &'n HashMap<char, Node>,
+    removed_edges: HashMap<char, Vec<char>>,
+    no_incoming: BinaryHeap<&'n Node>,
+    in_progress: VecDeque<InProgress>,
+    now: usize,
+    finished: usize,
+}
+
+struct InProgress {
+    node: char,
+    done_at: usize,
+}
+
+impl<'n> TopoSort<'n> {
+    /// The order in which a single worker does the steps, always taking the
+    /// alphabetically first available one, or `None` if the steps form a
+    /// cycle.
+    pub fn topological_sort(nodes: &'n HashMap<char, Node>) -> Option<String> {
+        Self::new(nodes).topological_sort_helper()
+    }
+
+    /// The time it takes `n_workers` to do all steps, when each step takes
+    /// `Node::time_cost(base_cost)` seconds, or `None` if the steps form a
+    /// cycle.
+    pub fn topological_worker_sort(
+        nodes: &'n HashMap<char, Node>,
+        n_workers: usize,
+        base_cost: usize,
+    ) -> Option<usize> {
+        Self::new(nodes).topological_worker_sort_helper(n_workers, base_cost)
+    }
+
+    fn new(nodes: &'n HashMap<char, Node>) -> Self {
+        TopoSort {
+            nodes,
+            removed_edges: HashMap::new(),
+            no_incoming: BinaryHeap::new(),
+            in_progress: VecDeque::new(),
+            now: 0usize,
+            finished: 0,
+        }
+    }
+
+    fn remove_node(&mut self, to_remove: char) {
+        self.finished += 1;
+        for node in self.nodes.values() {
+            if node.incoming.contains(&to_remove) {
+                let removed_from_node = self.removed_edges.entry(node.name).or_default();
+                removed_from_node.push(to_remove);
+                if node.incoming.iter().all(|i| removed_from_node.contains(i)) {
+                    self.no_incoming.push(node);
+                }
+            }
+        }
+    }
+
+    /// Finishes the step that is done first, along with any others that
+    /// are done at the same time.
+    fn perform_queued_step(&mut self) {
+        let performed = self.in_progress.pop_front().unwrap();
+        self.now = performed.done_at;
+        self.remove_node(performed.node);
+        while self.in_progress.front().map(|p| p.done_at) == Some(self.now) {
+            let performed = self.in_progress.pop_front().unwrap();
+            self.remove_node(performed.node);
+        }
+    }
+
+    /// Starts a step, keeping `in_progress` ordered by when steps are done.
+    fn start_step(&mut self, node: char, time_cost: usize) {
+        let done_at = self.now + time_cost;
+        let ix = self
+            .in_progress
+            .iter()
+            .position(|p| p.done_at > done_at)
+            .unwrap_or(self.in_progress.len());
+        self.in_progress.insert(ix, InProgress { node, done_at });
+    }
+
+    fn topological_sort_helper(&mut self) -> Option<String> {
+        let mut result = String::new();
+        for node in self.nodes.values() {
+            if node.incoming.is_empty() {
+                self.no_incoming.push(node);
+            }
+        }
+        while let Some(top) = self.no_incoming.pop() {
+            result.push(top.name);
+            self.remove_node(top.name);
+        }
+        Some(result).filter(|_| self.finished == self.nodes.len())
+    }
+
+    fn topological_worker_sort_helper(
+        &mut self,
+        n_workers: usize,
+        base_cost: usize,
+    ) -> Option<usize> {
+        for node in self.nodes.values() {
+            if node.incoming.is_empty() {
+                self.no_incoming.push(node);
+            }
+        }
+        loop {
+            if self.in_progress.is_empty() && self.no_incoming.is_empty() {
+                break;
+            }
+            match self.no_incoming.pop() {
+                Some(top) => self.start_step(top.name, top.time_cost(base_cost)),
+                None => {
+                    self.perform_queued_step();
+                }
+            }
+            if self.in_progress.len() == n_workers {
+                self.perform_queued_step();
+            }
+        }
+        Some(self.now).filter(|_| self.finished == self.nodes.len())
+    }
+}
+
+pub const N_WORKERS: usize = 5;
+pub const BASE_COST: usize = 60;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Edge>;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse_records(input)?)
+    }
+
+    fn part1(edges: &Self::Input) -> aoc18_common::Result<String> {
+        Ok(TopoSort::topological_sort(&create_dag(edges)).ok_or("The steps form a cycle")?)
+    }
+
+    fn part2(edges: &Self::Input) -> aoc18_common::Result<usize> {
+        Ok(
+            TopoSort::topological_worker_sort(&create_dag(edges), N_WORKERS, BASE_COST)
+                .ok_or("The steps form a cycle")?,
+        )
+    }
+}
+
+impl Generator for Day07 {
+    /// The edges between `size` steps, which is at most 26 as steps are
+    /// letters. Every step but the first depends on at least one earlier
+    /// step, so all of them appear in an edge. The lines are shuffled.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut names: Vec<char> = (b'A'..=b'Z').map(|c| c as char).collect();
+        rng.shuffle(&mut names);
+        names.truncate(size.clamp(2, 26));
+        let mut edges = Vec::new();
+        for (ix, &after) in names.iter().enumerate().skip(1) {
+            let required = rng.below(ix);
+            for (before_ix, &before) in names[..ix].iter().enumerate() {
+                if before_ix == required || rng.chance(0.15) {
+                    edges.push(Edge { before, after });
+                }
+            }
+        }
+        rng.shuffle(&mut edges);
+        edges.iter().map(|edge| format!("{}\n", edge)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+    #[test]
+    fn example() {
+        let edges = Day07::parse(EXAMPLE).unwrap();
+        let dag = create_dag(&edges);
+        assert_eq!(TopoSort::topological_sort(&dag).unwrap(), "CABDFE");
+        assert_eq!(TopoSort::topological_worker_sort(&dag, 2, 0), Some(15));
+    }
+
+    #[test]
+    fn cycle() {
+        let edges = Day07::parse(&format!(
+            "{}\nStep E must be finished before step C can begin.",
+            EXAMPLE
+        ))
+        .unwrap();
+        assert!(Day07::part1(&edges).is_err());
+        assert!(Day07::part2(&edges).is_err());
+        let edges = Day07::parse("Step A must be finished before step A can begin.").unwrap();
+        let dag = create_dag(&edges);
+        assert_eq!(TopoSort::topological_sort(&dag), None);
+        assert_eq!(TopoSort::topological_worker_sort(&dag, 2, 0), None);
+    }
+
+    #[test]
+    fn generated_input() {
+        let edges = Day07::parse(&Day07::generate(&mut Rng::new(1), 26)).unwrap();
+        let dag = create_dag(&edges);
+        assert_eq!(dag.len(), 26);
+        assert_eq!(TopoSort::topological_sort(&dag).unwrap().len(), 26);
+        assert!(TopoSort::topological_worker_sort(&dag, N_WORKERS, BASE_COST).unwrap() > 0);
+    }
+}
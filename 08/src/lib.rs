@@ -0,0 +1,205 @@
+//! Day 8: Memory Maneuver.
+
+extern crate aoc18_common;
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use std::num::ParseIntError;
+
+use aoc18_common::{parse_record, Generator, Rng, Solution};
+
+/// Why the input could not be parsed as a license tree.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Number(ParseIntError),
+    Truncated,
+    TrailingData(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Number(err) => write!(f, "Bad number ({})", err),
+            Error::Truncated => write!(f, "Tree ends in the middle of a node"),
+            Error::TrailingData(n) => write!(f, "{} numbers left after the root node", n),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Number(err)
+    }
+}
+
+/// A node of the license tree.
+pub struct Node {
+    pub metadata: Vec<u8>,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Reads a node from the start of `input`, returning it along with the
+    /// numbers that follow it.
+    pub fn new(input: &[u8]) -> Result<(Self, &[u8]), Error> {
+        if input.len() < 2 {
+            return Err(Error::Truncated);
+        }
+        let n_children = input[0];
+        let n_metadata = input[1] as usize;
+        let (_, mut input) = input.split_at(2);
+        let mut children = Vec::new();
+        for _i in 0..n_children {
+            let (child, rest_input) = Node::new(input)?;
+            children.push(child);
+            input = rest_input;
+        }
+        if input.len() < n_metadata {
+            return Err(Error::Truncated);
+        }
+        let (metadata_input, input) = input.split_at(n_metadata);
+        let node = Node {
+            metadata: metadata_input.to_vec(),
+            children,
+        };
+        Ok((node, input))
+    }
+
+    /// The sum of the metadata of this node and all its descendants.
+    pub fn metadata_sum(&self) -> u32 {
+        let own_sum: u32 = self.metadata.iter().map(|&m| m as u32).sum();
+        let children_sums: u32 = self.children.iter().map(|c| c.metadata_sum()).sum();
+        own_sum + children_sums
+    }
+
+    /// The value of the node: its metadata sum if it has no children, else
+    /// the sum of the values of the children its metadata refers to, from 1.
+    /// Entries of 0 or past the last child count for nothing.
+    pub fn metadata_sum_part2(&self) -> u32 {
+        if self.children.is_empty() {
+            self.metadata.iter().map(|&m| m as u32).sum()
+        } else {
+            self.metadata
+                .iter()
+                .filter_map(|&m| m.checked_sub(1))
+                .map(|m| m as usize)
+                .filter(|&m| m < self.children.len())
+                .map(|m| self.children[m].metadata_sum_part2())
+                .sum()
+        }
+    }
+}
+
+impl FromStr for Node {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s
+            .split(' ')
+            .map(|n| n.parse())
+            .collect::<Result<Vec<u8>, _>>()?;
+        let (node, rest) = Node::new(&input)?;
+        if !rest.is_empty() {
+            return Err(Error::TrailingData(rest.len()));
+        }
+        Ok(node)
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Node;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse_record(input)?)
+    }
+
+    fn part1(tree: &Self::Input) -> aoc18_common::Result<u32> {
+        Ok(tree.metadata_sum())
+    }
+
+    fn part2(tree: &Self::Input) -> aoc18_common::Result<u32> {
+        Ok(tree.metadata_sum_part2())
+    }
+}
+
+/// Appends a random node with `size` nodes in total, counting itself and
+/// all its descendants.
+fn generate_node(rng: &mut Rng, size: usize, numbers: &mut Vec<u8>) {
+    let rest = size - 1;
+    let n_children = if rest == 0 {
+        0
+    } else {
+        1 + rng.below(rest.min(7))
+    };
+    let mut child_sizes = vec![1; n_children];
+    for _ in n_children..rest {
+        child_sizes[rng.below(n_children)] += 1;
+    }
+    let n_metadata = rng.range(1, 11) as u8;
+    numbers.push(n_children as u8);
+    numbers.push(n_metadata);
+    for child_size in child_sizes {
+        generate_node(rng, child_size, numbers);
+    }
+    for _ in 0..n_metadata {
+        numbers.push(rng.range(1, 9) as u8);
+    }
+}
+
+impl Generator for Day08 {
+    /// A license tree of `size` nodes. Metadata entries are 1 to 9, so some
+    /// of them refer to children that don't exist, like in the real input.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut numbers = Vec::new();
+        generate_node(rng, size.max(1), &mut numbers);
+        let numbers: Vec<_> = numbers.iter().map(|n| n.to_string()).collect();
+        numbers.join(" ") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test]
+    fn example() {
+        let tree = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(tree.metadata_sum(), 138);
+        assert_eq!(tree.metadata_sum_part2(), 66);
+    }
+
+    #[test]
+    fn zero_metadata_entry() {
+        let tree: Node = "1 1 0 1 5 0".parse().unwrap();
+        assert_eq!(tree.metadata_sum_part2(), 0);
+        let tree: Node = "1 2 0 1 5 0 1".parse().unwrap();
+        assert_eq!(tree.metadata_sum_part2(), 5);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("2 3 0 3 10".parse::<Node>().err(), Some(Error::Truncated));
+        assert_eq!("0 1 5 7 7".parse::<Node>().err(), Some(Error::TrailingData(2)));
+    }
+
+    #[test]
+    fn generated_input() {
+        fn count(node: &Node) -> usize {
+            1 + node.children.iter().map(count).sum::<usize>()
+        }
+        let tree = Day08::parse(&Day08::generate(&mut Rng::new(1), 1000)).unwrap();
+        assert_eq!(count(&tree), 1000);
+        assert!(tree.metadata_sum_part2() <= 9 * 11 * 1000);
+    }
+}
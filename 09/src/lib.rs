@@ -0,0 +1,160 @@
+//! Day 9: Marble Mania.
+
+extern crate aoc18_common;
+
+use std::error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use aoc18_common::{parse_record, Generator, Rng, Solution, Unsolved};
+
+/// Why the input could not be parsed as a game.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Format,
+    Number(ParseIntError),
+    NoPlayers,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Format => write!(
+                f,
+                "Expected a game like \"9 players; last marble is worth 25 points\""
+            ),
+            Error::Number(err) => write!(f, "Bad number ({})", err),
+            Error::NoPlayers => write!(f, "There must be at least one player"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Number(err)
+    }
+}
+
+pub struct Game {
+    pub n_players: usize,
+    pub last_marble_value: u32,
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    // <n> players; last marble is worth <m> points
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        if words.len() != 8 {
+            return Err(Error::Format);
+        }
+        let n_players = words[0].parse()?;
+        let last_marble_value = words[6].parse()?;
+        if n_players == 0 {
+            return Err(Error::NoPlayers);
+        }
+        Ok(Game {
+            n_players,
+            last_marble_value,
+        })
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} players; last marble is worth {} points",
+            self.n_players, self.last_marble_value
+        )
+    }
+}
+
+/// The winning score of a game.
+pub fn part1(n_players: usize, last_marble_value: u32) -> u32 {
+    let mut marbles: Vec<u32> = vec![0];
+    let mut current_ix = 0;
+    let mut scores = vec![0; n_players];
+    let mut player_ix = 0;
+    for m_no in 1..(last_marble_value + 1) {
+        player_ix = (player_ix + 1) % n_players;
+        if m_no % 23 == 0 {
+            scores[player_ix] += m_no;
+            while current_ix < 7 {
+                current_ix += marbles.len();
+            }
+            current_ix -= 7;
+            let removed = marbles.remove(current_ix);
+            scores[player_ix] += removed;
+        } else {
+            current_ix = (current_ix + 2) % marbles.len();
+            marbles.insert(current_ix, m_no);
+        }
+    }
+    *scores.iter().max().unwrap()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Game;
+    type Part1 = u32;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse_record(input)?)
+    }
+
+    fn part1(game: &Self::Input) -> aoc18_common::Result<u32> {
+        Ok(part1(game.n_players, game.last_marble_value))
+    }
+
+    fn part2(_game: &Self::Input) -> aoc18_common::Result<Unsolved> {
+        Err(Unsolved.into())
+    }
+}
+
+impl Generator for Day09 {
+    /// A game of 2 to 500 players in which the last marble is worth `size`
+    /// points.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let game = Game {
+            n_players: rng.range(2, 500) as usize,
+            last_marble_value: size as u32,
+        };
+        format!("{}\n", game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_game() {
+        let game = Day09::parse("9 players; last marble is worth 25 points\n").unwrap();
+        assert_eq!((game.n_players, game.last_marble_value), (9, 25));
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1(9, 25), 32);
+        assert_eq!(part1(10, 1618), 8317);
+        assert_eq!(part1(13, 7999), 146373);
+        assert_eq!(part1(17, 1104), 2764);
+        assert_eq!(part1(21, 6111), 54718);
+        assert_eq!(part1(30, 5807), 37305);
+    }
+
+    #[test]
+    fn generated_input() {
+        let game = Day09::parse(&Day09::generate(&mut Rng::new(1), 5000)).unwrap();
+        assert_eq!(game.last_marble_value, 5000);
+        assert!(Day09::part1(&game).unwrap() > 0);
+    }
+}
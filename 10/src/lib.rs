@@ -0,0 +1,249 @@
+//! Day 10: The Stars Align.
+
+extern crate aoc18_common;
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+use std::error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::vec::Vec;
+use std::str::FromStr;
+
+use aoc18_common::{parse_records, Generator, Rng, Solution};
+use regex::Regex;
+
+/// Why a line could not be parsed as a point.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Format,
+    Number(ParseIntError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Format => write!(
+                f,
+                "Expected a point like \"position=< 9,  1> velocity=< 0,  2>\""
+            ),
+            Error::Number(err) => write!(f, "Bad number ({})", err),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Number(err)
+    }
+}
+
+/// A point of light with its position and velocity per second.
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub dx: i32,
+    pub dy: i32,
+}
+
+impl FromStr for Point {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref point_regex: Regex =
+                Regex::new("position=<(?P<x>.+), (?P<y>.+)> velocity=<(?P<dx>.+), (?P<dy>.+)>")
+                    .unwrap();
+        }
+        let captures = point_regex.captures(s).ok_or(Error::Format)?;
+        let x = captures["x"].trim().parse::<i32>()?;
+        let y = captures["y"].trim().parse::<i32>()?;
+        let dx = captures["dx"].trim().parse::<i32>()?;
+        let dy = captures["dy"].trim().parse::<i32>()?;
+        Ok(Point { x, y, dx, dy })
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>",
+            self.x, self.y, self.dx, self.dy
+        )
+    }
+}
+
+fn extent(points: &[Point], steps: i32) -> (i32, i32) {
+    let min_x = points.iter().map(|p| p.x + p.dx * steps).min().unwrap();
+    let max_x = points.iter().map(|p| p.x + p.dx * steps).max().unwrap();
+    let min_y = points.iter().map(|p| p.y + p.dy * steps).min().unwrap();
+    let max_y = points.iter().map(|p| p.y + p.dy * steps).max().unwrap();
+    (max_x - min_x, max_y - min_y)
+}
+
+fn extent_score(points: &[Point], steps: i32) -> i64 {
+    let ex = extent(points, steps);
+    (ex.0 as i64) * (ex.1 as i64)
+}
+
+/// The second within the first 20000 at which the points are closest
+/// together, which is when they spell out the message.
+pub fn best_time(points: &[Point]) -> i32 {
+    (0..20000).min_by_key(|&i| extent_score(points, i)).unwrap()
+}
+
+/// Draws the points as they are after `best_i` seconds, using `#` for
+/// lights and `.` for darkness.
+pub fn render(points: &[Point], best_i: i32) -> String {
+    let mut picture = String::new();
+    let answer_points: Vec<(i32, i32)> = points.
+        iter()
+        .map(|p| (p.x + best_i * p.dx, p.y + best_i * p.dy)).collect();
+    let min_x = answer_points.iter().map(|&(x, _)| x).min().unwrap();
+    let max_x = answer_points.iter().map(|&(x, _)| x).max().unwrap();
+    let min_y = answer_points.iter().map(|&(_, y)| y).min().unwrap();
+    let max_y = answer_points.iter().map(|&(_, y)| y).max().unwrap();
+
+    for y in min_y ..= max_y {
+        for x in min_x ..= max_x {
+            let pt = (x, y);
+            if answer_points.contains(&pt) {
+                picture.push('#');
+            } else {
+                picture.push('.');
+            }
+        }
+        picture.push('\n');
+    }
+    picture.pop();
+    picture
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Point>;
+    type Part1 = String;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        let points: Vec<Point> = parse_records(input)?;
+        if points.is_empty() {
+            return Err("No points in the input".into());
+        }
+        Ok(points)
+    }
+
+    fn part1(points: &Self::Input) -> aoc18_common::Result<String> {
+        Ok(render(points, best_time(points)))
+    }
+
+    fn part2(points: &Self::Input) -> aoc18_common::Result<i32> {
+        Ok(best_time(points))
+    }
+}
+
+impl Generator for Day10 {
+    /// `size` points that come together into a random 62 by 10 picture after
+    /// 10000 to 15000 seconds, like the message in the real input.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let time = rng.range(10000, 15000);
+        let mut text = String::new();
+        for _ in 0..size.max(1) {
+            let (x, y) = (rng.range(0, 61), rng.range(0, 9));
+            let (dx, dy) = loop {
+                let velocity = (rng.range(-5, 5), rng.range(-5, 5));
+                if velocity != (0, 0) {
+                    break velocity;
+                }
+            };
+            let point = Point {
+                x: (x - dx * time) as i32,
+                y: (y - dy * time) as i32,
+                dx: dx as i32,
+                dy: dy as i32,
+            };
+            text += &format!("{}\n", point);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+    const MESSAGE: &str = "\
+#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###";
+
+    #[test]
+    fn example() {
+        let points = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(best_time(&points), 3);
+        assert_eq!(render(&points, 3), MESSAGE);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Day10::parse("").is_err());
+        assert_eq!(
+            "position=< 9,  1>".parse::<Point>().err(),
+            Some(Error::Format)
+        );
+    }
+
+    #[test]
+    fn generated_input() {
+        let points = Day10::parse(&Day10::generate(&mut Rng::new(1), 200)).unwrap();
+        assert_eq!(points.len(), 200);
+        let time = best_time(&points);
+        assert!((10000..=15000).contains(&time));
+        let picture = render(&points, time);
+        assert!(picture.lines().count() <= 10);
+        assert!(picture.lines().all(|line| line.len() <= 62));
+    }
+}
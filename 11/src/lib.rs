@@ -0,0 +1,123 @@
+//! Day 11: Chronal Charge.
+
+extern crate aoc18_common;
+
+use std::vec::Vec;
+
+use aoc18_common::{parse_record, Generator, Rng, Solution, Unsolved};
+
+/// The power level of the fuel cell at `x`, `y` for a grid serial number.
+pub fn power_level(x: i32, y: i32, serial: i32) -> i32 {
+    let rack_id = x + 10;
+    let power = rack_id * y;
+    let power = power + serial;
+    let power = power * rack_id;
+    let power = (power / 100) % 10;
+    power - 5
+}
+
+fn block_sum(block: &[&[i32]]) -> i32 {
+    block.iter().map::<i32, _>(|row| row.iter().sum()).sum()
+}
+
+/// The top left corner and total power of the 3x3 square with the most
+/// power.
+pub fn part1(grid: &[[i32; 300]; 300]) -> (usize, usize, i32) {
+    // make dirty guess for clean code
+    let mut best_x = 0;
+    let mut best_y = 0;
+    let mut best = -99999999;
+    for (x, x_window) in grid.windows(3).enumerate() {
+        let x0_windows = x_window[0].windows(3);
+        let x1_windows = x_window[1].windows(3);
+        let x2_windows = x_window[2].windows(3);
+        for (y, ((b_0, b_1), b_2)) in x0_windows.zip(x1_windows).zip(x2_windows).enumerate() {
+            let res = block_sum(&[b_0, b_1, b_2]);
+            if res > best {
+                best_x = x;
+                best_y = y;
+                best = res;
+            }
+        }
+    }
+    (best_x, best_y, best)
+}
+
+#[allow(dead_code, unused_variables, unused_mut)]
+fn part2(grid: &[[i32; 300]; 300], size: usize) -> (usize, usize, i32) {
+    // make dirty guess for clean code
+    let mut best_x = 0;
+    let mut best_y = 0;
+    let mut best = -99999999;
+    for (x, x_window) in grid.windows(size).enumerate() {
+        let mut window_iterators: Vec<_> = x_window.iter().map(|w| w.windows(size)).collect();
+        let block: Option<Vec<_>> = window_iterators.iter_mut().map(|w_it| w_it.next()).collect();
+
+    }
+    (best_x, best_y, best)
+}
+
+/// The power levels of the fuel cells, indexed by `[x][y]`.
+pub fn build_grid(serial: i32) -> [[i32; 300]; 300] {
+    let mut grid: [[i32; 300]; 300] = [[0; 300]; 300];
+    for (x, column) in grid.iter_mut().enumerate().take(299) {
+        for (y, cell) in column.iter_mut().enumerate().take(299) {
+            *cell = power_level(x as i32, y as i32, serial);
+        }
+    }
+    grid
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = i32;
+    type Part1 = String;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse_record(input)?)
+    }
+
+    fn part1(&serial: &Self::Input) -> aoc18_common::Result<String> {
+        let (best_x, best_y, _) = part1(&build_grid(serial));
+        Ok(format!("{},{}", best_x, best_y))
+    }
+
+    fn part2(_serial: &Self::Input) -> aoc18_common::Result<Unsolved> {
+        Err(Unsolved.into())
+    }
+}
+
+impl Generator for Day11 {
+    /// A grid serial number. The input has no size, so `size` is ignored.
+    fn generate(rng: &mut Rng, _size: usize) -> String {
+        format!("{}\n", rng.range(1, 9999))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn power_level_examples() {
+        assert_eq!(power_level(3, 5, 8), 4);
+        assert_eq!(power_level(122, 79, 57), -5);
+        assert_eq!(power_level(217, 196, 39), 0);
+        assert_eq!(power_level(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1(&build_grid(18)), (33, 45, 29));
+        assert_eq!(part1(&build_grid(42)), (21, 61, 30));
+        assert_eq!(Day11::part1(&18).unwrap(), "33,45");
+    }
+
+    #[test]
+    fn generated_input() {
+        let serial = Day11::parse(&Day11::generate(&mut Rng::new(1), 0)).unwrap();
+        assert!((1..10000).contains(&serial));
+    }
+}
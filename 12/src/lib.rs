@@ -0,0 +1,388 @@
+//! Day 12: Subterranean Sustainability.
+
+extern crate aoc18_common;
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc18_common::{parse_lines, Generator, ParseError, Rng, Solution};
+
+/// Why a line could not be parsed as the initial state or a note.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    InitialState,
+    Note,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InitialState => write!(
+                f,
+                "Expected an initial state like \"initial state: #..#.#..##\""
+            ),
+            Error::Note => write!(f, "Expected a note like \"...## => #\""),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// A pot and the two pots on either side of it, `true` meaning a plant.
+pub type Rule = [bool; 5];
+
+fn parse_pots(s: &str) -> Option<Vec<bool>> {
+    s.chars()
+        .map(|c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_init_state(line: &str) -> Result<Vec<bool>, Error> {
+    if !line.starts_with("initial state: ") {
+        return Err(Error::InitialState);
+    }
+    parse_pots(&line["initial state: ".len()..]).ok_or(Error::InitialState)
+}
+
+/// A note saying whether a pot has a plant in the next generation when it
+/// and its neighbours match `pattern`.
+pub struct Note {
+    pub pattern: Rule,
+    pub result: bool,
+}
+
+impl FromStr for Note {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(" => ").collect();
+        if parts.len() != 2 {
+            return Err(Error::Note);
+        }
+        let pots = parse_pots(parts[0]).ok_or(Error::Note)?;
+        let result = parse_pots(parts[1]).ok_or(Error::Note)?;
+        if pots.len() != 5 || result.len() != 1 {
+            return Err(Error::Note);
+        }
+        let mut pattern = [false; 5];
+        pattern.copy_from_slice(&pots);
+        Ok(Note {
+            pattern,
+            result: result[0],
+        })
+    }
+}
+
+fn pots(pots: &[bool]) -> String {
+    pots.iter().map(|&plant| if plant { '#' } else { '.' }).collect()
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} => {}", pots(&self.pattern), pots(&[self.result]))
+    }
+}
+
+/// The patterns that lead to a plant in the next generation.
+pub struct RuleLookup {
+    rules: Vec<Rule>
+}
+
+impl RuleLookup {
+    pub fn new(notes: &[Note]) -> Self {
+        let mut rules: Vec<_> = notes
+            .iter()
+            .filter(|note| note.result)
+            .map(|note| note.pattern)
+            .collect();
+        rules.sort();
+        RuleLookup { rules }
+    }
+
+    /// Whether the pot in the middle of `state` has a plant next generation.
+    pub fn rule(&self, state: &[bool; 5]) -> bool {
+        self.rules.contains(state)
+    }
+}
+
+#[allow(dead_code)]
+fn print_state(state: &[bool], start_ix: isize) {
+    for _ in -15..start_ix {
+        print!(".");
+    }
+    for &b in state {
+        if b {
+            print!("#");
+        } else {
+            print!(".");
+        }
+    }
+    println!();
+}
+
+fn parse(input: &str) -> Result<(Vec<bool>, RuleLookup), ParseError<Error>> {
+    let lines: Vec<&str> = input.lines().collect();
+    let first_line = lines.first().cloned().unwrap_or("");
+    let init_state =
+        parse_init_state(first_line).map_err(|reason| ParseError::at(0, first_line, reason))?;
+    let note_lines = lines
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(ix, line)| (ix, *line));
+    let notes: Vec<Note> = parse_lines(note_lines)?;
+    Ok((init_state, RuleLookup::new(&notes)))
+}
+
+/// The pots from the first plant to the last, where the first is number
+/// `first`.
+#[derive(Clone)]
+struct Pots {
+    plants: Vec<bool>,
+    first: isize,
+}
+
+impl Pots {
+    fn new(init_state: &[bool]) -> Self {
+        Pots {
+            plants: init_state.to_vec(),
+            first: 0,
+        }
+        .trimmed()
+    }
+
+    fn trimmed(mut self) -> Self {
+        let Some(last) = self.plants.iter().rposition(|&b| b) else {
+            return Pots {
+                plants: Vec::new(),
+                first: 0,
+            };
+        };
+        self.plants.truncate(last + 1);
+        let skip = self.plants.iter().position(|&b| b).unwrap();
+        self.plants.drain(..skip);
+        self.first += skip as isize;
+        self
+    }
+
+    fn next(&self, rule_lookup: &RuleLookup) -> Self {
+        let mut padded = vec![false; 4];
+        padded.extend(&self.plants);
+        padded.extend(&[false; 4]);
+        let plants = padded
+            .windows(5)
+            .map(|w| rule_lookup.rule(&[w[0], w[1], w[2], w[3], w[4]]))
+            .collect();
+        Pots {
+            plants,
+            first: self.first - 2,
+        }
+        .trimmed()
+    }
+
+    fn sum(&self) -> isize {
+        (self.first..)
+            .zip(&self.plants)
+            .filter(|&(_, &b)| b)
+            .map(|(i, _)| i)
+            .sum()
+    }
+}
+
+/// The pots after growing `pots` for `generations`, skipping ahead once the
+/// plants repeat an earlier pattern, possibly shifted. Gives up after
+/// `patience` generations without finding one.
+///
+/// Only one earlier generation is kept to compare with, and it is moved up
+/// to the current one at every power of two (Brent's algorithm), so any
+/// cycle is found within a few times the generations it takes to enter it.
+fn grow_pots(
+    mut pots: Pots,
+    rule_lookup: &RuleLookup,
+    generations: usize,
+    patience: usize,
+) -> Option<Pots> {
+    let mut saved = pots.clone();
+    let mut saved_at = 0;
+    let mut generation = 0;
+    while generation < generations {
+        if generation == patience {
+            return None;
+        }
+        pots = pots.next(rule_lookup);
+        generation += 1;
+        if pots.plants == saved.plants {
+            let period = generation - saved_at;
+            let shift = pots.first - saved.first;
+            let periods = (generations - generation) / period;
+            for _ in 0..(generations - generation) % period {
+                pots = pots.next(rule_lookup);
+            }
+            pots.first += periods as isize * shift;
+            return Some(pots);
+        }
+        if generation.is_power_of_two() {
+            saved = pots.clone();
+            saved_at = generation;
+        }
+    }
+    Some(pots)
+}
+
+/// The sum of the numbers of the pots with plants after `generations`,
+/// where the first pot of `init_state` is number 0. This is quick for any
+/// number of generations once the plants settle into a cycle.
+pub fn grow(init_state: &[bool], rule_lookup: &RuleLookup, generations: usize) -> isize {
+    grow_pots(Pots::new(init_state), rule_lookup, generations, usize::MAX)
+        .unwrap()
+        .sum()
+}
+
+/// The generations part 2 asks about.
+pub const PART2_GENERATIONS: usize = 5_000_000;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Vec<bool>, RuleLookup);
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1((init_state, rule_lookup): &Self::Input) -> aoc18_common::Result<isize> {
+        Ok(grow(init_state, rule_lookup, 20))
+    }
+
+    fn part2((init_state, rule_lookup): &Self::Input) -> aoc18_common::Result<isize> {
+        Ok(grow(init_state, rule_lookup, PART2_GENERATIONS))
+    }
+}
+
+/// How many generations the plants of a generated input may take to settle
+/// into a cycle.
+const GENERATED_PATIENCE: usize = 200;
+
+fn random_notes(rng: &mut Rng) -> Vec<Note> {
+    (0..32)
+        .map(|bits| {
+            let mut pattern = [false; 5];
+            for (ix, pot) in pattern.iter_mut().enumerate() {
+                *pot = bits & (1 << (4 - ix)) != 0;
+            }
+            Note {
+                pattern,
+                result: bits != 0 && rng.chance(0.5),
+            }
+        })
+        .collect()
+}
+
+impl Generator for Day12 {
+    /// An initial state of `size` pots, starting with a plant, and notes for
+    /// all 32 patterns, with `.....` leading to an empty pot so that the
+    /// plants stay finite. Like the real input, the plants soon repeat a
+    /// pattern, usually drifting along, so that part 2 is quick; inputs where
+    /// they grow chaotically or die out are drawn again.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let (init_state, notes) = loop {
+            let init_state: Vec<bool> = (0..size.max(1))
+                .map(|ix| ix == 0 || rng.chance(0.5))
+                .collect();
+            let notes = random_notes(rng);
+            let settled = grow_pots(
+                Pots::new(&init_state),
+                &RuleLookup::new(&notes),
+                PART2_GENERATIONS,
+                GENERATED_PATIENCE,
+            );
+            if settled.is_some_and(|pots| !pots.plants.is_empty()) {
+                break (init_state, notes);
+            }
+        };
+        let mut text = format!("initial state: {}\n\n", pots(&init_state));
+        for note in &notes {
+            text += &format!("{}\n", note);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #";
+
+    #[test]
+    fn example() {
+        let (init_state, rule_lookup) = parse(EXAMPLE).unwrap();
+        assert_eq!(init_state.len(), 25);
+        assert_eq!(rule_lookup.rules.len(), 14);
+        assert_eq!(grow(&init_state, &rule_lookup, 0), 145);
+        assert_eq!(grow(&init_state, &rule_lookup, 20), 325);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("initial state #..#").err().unwrap();
+        assert_eq!((err.line, err.reason), (1, Error::InitialState));
+        let err = parse("initial state: #..#\n\n...## => #\n..#. => #").err().unwrap();
+        assert_eq!((err.line, err.reason), (4, Error::Note));
+    }
+
+    #[test]
+    fn generated_input() {
+        let input = Day12::generate(&mut Rng::new(1), 100);
+        let (init_state, rule_lookup) = parse(&input).unwrap();
+        assert_eq!(init_state.len(), 100);
+        assert!(!rule_lookup.rule(&[false; 5]));
+        grow(&init_state, &rule_lookup, 20);
+        for &size in &[3, 50, 100] {
+            for seed in 0..5 {
+                let input = Day12::parse(&Day12::generate(&mut Rng::new(seed), size)).unwrap();
+                Day12::part2(&input).unwrap();
+            }
+        }
+        let (init_state, rule_lookup) = parse(&Day12::generate(&mut Rng::new(3), 20)).unwrap();
+        let mut pots = Pots::new(&init_state);
+        for _ in 0..GENERATED_PATIENCE {
+            pots = pots.next(&rule_lookup);
+        }
+        assert_eq!(grow(&init_state, &rule_lookup, GENERATED_PATIENCE), pots.sum());
+    }
+
+    #[test]
+    fn skipping_ahead() {
+        let (init_state, rule_lookup) = parse(EXAMPLE).unwrap();
+        let mut pots = Pots::new(&init_state);
+        for generation in 0..=300 {
+            assert_eq!(grow(&init_state, &rule_lookup, generation), pots.sum());
+            pots = pots.next(&rule_lookup);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Day 14: Chocolate Charts.
+
+extern crate aoc18_common;
+
+use std::char;
+use std::collections::VecDeque;
+use std::mem;
+
+use aoc18_common::{Generator, Rng, Solution};
+
+/// The scores of the new recipes the two elves create, one at a time.
+pub struct RecipeIterator {
+    recipes: Vec<u32>,
+    elf1_ix: usize,
+    elf2_ix: usize,
+    waiting: Option<u32>,
+}
+
+impl RecipeIterator {
+    /// Starts from the recipes in `seed`, or `None` unless it is at least
+    /// two digits.
+    pub fn new(seed: &str) -> Option<Self> {
+        let digits = seed
+            .chars()
+            .map(|c| c.to_digit(10))
+            .collect::<Option<Vec<_>>>()?;
+        if digits.len() < 2 {
+            return None;
+        }
+        Some(RecipeIterator {
+            recipes: digits,
+            elf1_ix: 0,
+            elf2_ix: 1,
+            waiting: None,
+        })
+    }
+}
+
+impl Iterator for RecipeIterator {
+    type Item = u32;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.waiting.is_some() {
+            let mut taken_waiting = None;
+            mem::swap(&mut self.waiting, &mut taken_waiting);
+            return taken_waiting;
+        }
+        let elf1_recipe = self.recipes[self.elf1_ix] as usize;
+        let elf2_recipe = self.recipes[self.elf2_ix] as usize;
+        let sum = (elf1_recipe + elf2_recipe) as u32;
+
+        let new_recipe = if sum > 9 { sum / 10 } else { sum };
+        self.waiting = if sum > 9 { Some(sum % 10) } else { None };
+        self.recipes.push(new_recipe);
+        if let Some(waiting) = self.waiting {
+            self.recipes.push(waiting);
+        }
+        self.elf1_ix = (self.elf1_ix + elf1_recipe + 1) % self.recipes.len();
+        self.elf2_ix = (self.elf2_ix + elf2_recipe + 1) % self.recipes.len();
+        Some(new_recipe)
+    }
+}
+
+/// The scores of the ten recipes after the first `n_recipes`, or `None` if
+/// that is fewer than the two recipes the elves start with.
+pub fn part1(n_recipes: usize) -> Option<String> {
+    let mut answer = String::new();
+    let recipegenerator = RecipeIterator::new("37").unwrap();
+    for x in recipegenerator.skip(n_recipes.checked_sub(2)?).take(10) {
+        answer.push(char::from_digit(x, 10).unwrap());
+    }
+    Some(answer)
+}
+
+/// The number of recipes before the scores in `needle` first appear,
+/// counting the two the elves start with.
+pub fn part2(needle: &[u32]) -> u32 {
+    if needle.is_empty() {
+        return 0;
+    }
+    let recipegenerator = RecipeIterator::new("37").unwrap();
+    let mut scores = [3, 7].iter().cloned().chain(recipegenerator);
+    let mut last_seen: VecDeque<u32> = VecDeque::with_capacity(needle.len() + 1);
+    let end = scores
+        .position(|x| {
+            last_seen.push_back(x);
+            if last_seen.len() > needle.len() {
+                last_seen.pop_front();
+            }
+            last_seen.iter().eq(needle)
+        })
+        .unwrap();
+    (end + 1 - needle.len()) as u32
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = String;
+    type Part1 = String;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        let puzzle = input.trim();
+        if puzzle.is_empty() || !puzzle.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Expected a number of recipes: {:?}", puzzle).into());
+        }
+        Ok(puzzle.to_string())
+    }
+
+    fn part1(puzzle: &Self::Input) -> aoc18_common::Result<String> {
+        Ok(part1(puzzle.parse()?).ok_or("Part 1 needs at least 2 recipes")?)
+    }
+
+    fn part2(puzzle: &Self::Input) -> aoc18_common::Result<u32> {
+        let needle: Vec<u32> = puzzle.chars().filter_map(|c| c.to_digit(10)).collect();
+        Ok(part2(&needle))
+    }
+}
+
+impl Generator for Day14 {
+    /// A puzzle input of `size` digits, at most 7 so that part 1 stays
+    /// within memory. It is at least 2, which part 1 needs.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.clamp(1, 7);
+        let first = if size == 1 { rng.range(2, 9) } else { rng.range(1, 9) };
+        let mut text = first.to_string();
+        for _ in 1..size {
+            text += &rng.range(0, 9).to_string();
+        }
+        text + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1(9).unwrap(), "5158916779");
+        assert_eq!(part1(5).unwrap(), "0124515891");
+        assert_eq!(part1(18).unwrap(), "9251071085");
+        assert_eq!(part1(2018).unwrap(), "5941429882");
+        assert_eq!(part1(2).unwrap(), "1010124515");
+    }
+
+    #[test]
+    fn seeds() {
+        assert!(RecipeIterator::new("").is_none());
+        assert!(RecipeIterator::new("5").is_none());
+        assert!(RecipeIterator::new("3x").is_none());
+        let scores: Vec<u32> = RecipeIterator::new("37").unwrap().take(4).collect();
+        assert_eq!(scores, [1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn too_few_recipes() {
+        assert_eq!(part1(1), None);
+        let puzzle = Day14::parse("1").unwrap();
+        assert!(Day14::part1(&puzzle).is_err());
+        assert_eq!(Day14::part2(&puzzle).unwrap(), 2);
+    }
+
+    #[test]
+    fn part2_examples() {
+        let digits = |s: &str| -> Vec<u32> { s.chars().filter_map(|c| c.to_digit(10)).collect() };
+        assert_eq!(part2(&digits("51589")), 9);
+        assert_eq!(part2(&digits("01245")), 5);
+        assert_eq!(part2(&digits("92510")), 18);
+        assert_eq!(part2(&digits("59414")), 2018);
+        assert_eq!(part2(&digits("37")), 0);
+        assert_eq!(part2(&digits("7101")), 1);
+    }
+
+    #[test]
+    fn generated_input() {
+        for seed in 0..5 {
+            let puzzle = Day14::parse(&Day14::generate(&mut Rng::new(seed), 4)).unwrap();
+            assert_eq!(puzzle.len(), 4);
+            Day14::part1(&puzzle).unwrap();
+            Day14::part2(&puzzle).unwrap();
+        }
+    }
+}
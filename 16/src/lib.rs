@@ -0,0 +1,538 @@
+//! Day 16: Chronal Classification.
+
+extern crate aoc18_common;
+
+use std::collections::HashSet;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use std::num::ParseIntError;
+
+use aoc18_common::{parse_lines, Generator, ParseError, Rng, Solution};
+
+/// Why a line of the samples or the test program could not be parsed.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Memory,
+    Instruction,
+    Number(ParseIntError),
+    Opcode(u32),
+    Register(u32),
+    Truncated,
+    NoProgram,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Memory => write!(f, "Expected registers like \"Before: [3, 2, 1, 1]\""),
+            Error::Instruction => write!(f, "Expected an instruction like \"9 2 1 2\""),
+            Error::Number(err) => write!(f, "Bad number ({})", err),
+            Error::Opcode(op) => write!(f, "Opcode number {} is out of range", op),
+            Error::Register(reg) => write!(f, "Register {} is out of range", reg),
+            Error::Truncated => write!(f, "Sample ends early"),
+            Error::NoProgram => write!(f, "No test program after the samples"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Number(err)
+    }
+}
+
+/// The instructions of the device. Names ending in `r` take register `b`,
+/// those ending in `i` the value `b`; for the comparisons the two letters
+/// say which of `a` and `b` are registers.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+pub const ALL_OPCODES: &[Opcode] = &[
+    Opcode::Addr,
+    Opcode::Addi,
+    Opcode::Mulr,
+    Opcode::Muli,
+    Opcode::Banr,
+    Opcode::Bani,
+    Opcode::Borr,
+    Opcode::Bori,
+    Opcode::Setr,
+    Opcode::Seti,
+    Opcode::Gtir,
+    Opcode::Gtri,
+    Opcode::Gtrr,
+    Opcode::Eqir,
+    Opcode::Eqri,
+    Opcode::Eqrr,
+];
+
+impl Opcode {
+    pub fn is_a_reg(&self) -> bool {
+        use Opcode::*;
+        !matches!(self, Seti | Gtir | Eqir)
+    }
+    pub fn is_b_reg(&self) -> bool {
+        use Opcode::*;
+        !matches!(self, Addi | Muli | Bani | Bori | Setr | Seti | Gtri | Eqri)
+    }
+}
+
+/// The four registers.
+pub type MemState = [u32; 4];
+
+/// Executes an instruction, returning the registers afterwards, or `None`
+/// if `a` or `b` is out of range for a register operand or the result does
+/// not fit in a register.
+pub fn exec(op: Opcode, a: u32, b: u32, c: u32, state: MemState) -> Option<MemState> {
+    let mut res = state;
+    let op_result = exec_helper(op, a, b, state)?;
+    *res.get_mut(c as usize)? = op_result;
+    Some(res)
+}
+
+fn exec_helper(op: Opcode, a: u32, b: u32, state: MemState) -> Option<u32> {
+    use Opcode::*;
+    if (a >= 4 && op.is_a_reg()) || (b >= 4 && op.is_b_reg()) {
+        return None;
+    }
+    let a_reg = if a < 4 { state[a as usize] } else { 129 };
+    let b_reg = if b < 4 { state[b as usize] } else { 129 };
+    let value = match op {
+        Addr => a_reg.checked_add(b_reg)?,
+        Addi => a_reg.checked_add(b)?,
+        Mulr => a_reg.checked_mul(b_reg)?,
+        Muli => a_reg.checked_mul(b)?,
+        Banr => a_reg & b_reg,
+        Bani => a_reg & b,
+        Borr => a_reg | b_reg,
+        Bori => a_reg | b,
+        Setr => a_reg,
+        Seti => a,
+        Gtir => (a > b_reg) as u32,
+        Gtri => (a_reg > b) as u32,
+        Gtrr => (a_reg > b_reg) as u32,
+        Eqir => (a == b_reg) as u32,
+        Eqri => (a_reg == b) as u32,
+        Eqrr => (a_reg == b_reg) as u32,
+    };
+    Some(value)
+}
+
+/// The registers before and after executing an instruction with an
+/// unknown opcode.
+#[derive(Debug)]
+pub struct Sample {
+    pub before: MemState,
+    pub after: MemState,
+    pub op_number: u32,
+    pub a: u32,
+    pub b: u32,
+    pub c: u32,
+}
+
+/// An instruction of the test program.
+pub struct Instruction {
+    pub op_number: u32,
+    pub a: u32,
+    pub b: u32,
+    pub c: u32,
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vals = s
+            .split(' ')
+            .map(|num| num.parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()?;
+        if vals.len() != 4 {
+            return Err(Error::Instruction);
+        }
+        if vals[0] >= 16 {
+            return Err(Error::Opcode(vals[0]));
+        }
+        if vals[3] >= 4 {
+            return Err(Error::Register(vals[3]));
+        }
+        Ok(Instruction {
+            op_number: vals[0],
+            a: vals[1],
+            b: vals[2],
+            c: vals[3],
+        })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.op_number, self.a, self.b, self.c)
+    }
+}
+
+fn parse_mem_line(line: &str, prefix: &str) -> Result<MemState, Error> {
+    if !line.starts_with(prefix) {
+        return Err(Error::Memory);
+    }
+    let registers = line[prefix.len()..].trim();
+    if !registers.starts_with('[') || !registers.ends_with(']') {
+        return Err(Error::Memory);
+    }
+    let nums = registers[1..registers.len() - 1]
+        .split(", ")
+        .map(|num| num.parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    if nums.len() != 4 {
+        return Err(Error::Memory);
+    }
+    Ok([nums[0], nums[1], nums[2], nums[3]])
+}
+
+impl Sample {
+    /// Parses a sample from `lines`, the first of which has the 0-based
+    /// index `first_ix` in the input.
+    fn from_lines(first_ix: usize, lines: &[&str]) -> Result<Self, ParseError<Error>> {
+        let error_at = |offset: usize, reason| {
+            ParseError::at(first_ix + offset, lines.get(offset).unwrap_or(&""), reason)
+        };
+        if lines.len() < 3 {
+            return Err(error_at(lines.len(), Error::Truncated));
+        }
+        let mem_before = parse_mem_line(lines[0], "Before:").map_err(|r| error_at(0, r))?;
+        let instr: Instruction = lines[1].parse().map_err(|r| error_at(1, r))?;
+        let mem_after = parse_mem_line(lines[2], "After:").map_err(|r| error_at(2, r))?;
+        Ok(Sample {
+            before: mem_before,
+            after: mem_after,
+            op_number: instr.op_number,
+            a: instr.a,
+            b: instr.b,
+            c: instr.c,
+        })
+    }
+}
+
+impl fmt::Display for Sample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let instr = Instruction {
+            op_number: self.op_number,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+        };
+        writeln!(f, "Before: {:?}", self.before)?;
+        writeln!(f, "{}", instr)?;
+        write!(f, "After:  {:?}", self.after)
+    }
+}
+
+fn match_sample(sample: &Sample, op: &Opcode) -> bool {
+    let exec_result = exec(*op, sample.a, sample.b, sample.c, sample.before);
+    exec_result == Some(sample.after)
+}
+
+/// The opcodes that behave like the sample.
+pub fn sample_matches(sample: &Sample) -> Vec<Opcode> {
+    ALL_OPCODES
+        .iter()
+        .filter(|&op| match_sample(sample, op))
+        .copied()
+        .collect()
+}
+
+/// The number of samples that behave like three or more opcodes.
+pub fn part1(samples: &[Sample]) -> usize {
+    samples
+        .iter()
+        .map(|s| sample_matches(s).len())
+        .filter(|&len| len >= 3)
+        .count()
+}
+
+/// The opcode of each opcode number, if the samples determine them all.
+pub fn figure_out(samples: &[Sample]) -> Option<Vec<Opcode>> {
+    let mut possible: Vec<HashSet<Opcode>> = (0..16)
+        .map(|_| ALL_OPCODES.iter().copied().collect())
+        .collect();
+    for sample in samples {
+        let possible_insns = sample_matches(sample);
+        let possible_insns_set: HashSet<Opcode> = possible_insns.iter().copied().collect();
+        possible[sample.op_number as usize] = possible[sample.op_number as usize]
+            .intersection(&possible_insns_set)
+            .copied()
+            .collect();
+    }
+    satisfy(&mut possible)
+}
+
+fn satisfy(sets: &mut [HashSet<Opcode>]) -> Option<Vec<Opcode>> {
+    let mut res = vec![Opcode::Addi; 16];
+    for _i in 0..16 {
+        let singleton_pos = sets.iter().position(|s| s.len() == 1)?;
+        let figured_code = sets[singleton_pos].drain().next().unwrap();
+        for set in sets.iter_mut() {
+            set.remove(&figured_code);
+        }
+        res[singleton_pos] = figured_code;
+    }
+    Some(res)
+}
+
+fn parse(input: &str) -> Result<(Vec<Sample>, Vec<Instruction>), ParseError<Error>> {
+    let lines: Vec<&str> = input.lines().collect();
+    // The samples are separated from the test program by three blank lines.
+    let split_ix = lines
+        .windows(3)
+        .position(|w| w.iter().all(|line| line.is_empty()))
+        .ok_or_else(|| ParseError::at(lines.len(), "", Error::NoProgram))?;
+    let samples = lines[..split_ix]
+        .chunks(4)
+        .enumerate()
+        .map(|(chunk_ix, chunk)| Sample::from_lines(4 * chunk_ix, chunk))
+        .collect::<Result<Vec<_>, _>>()?;
+    let program_lines = lines
+        .iter()
+        .enumerate()
+        .skip(split_ix)
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(ix, line)| (ix, *line));
+    let instructions = parse_lines(program_lines)?;
+    Ok((samples, instructions))
+}
+
+/// Why the test program could not be run.
+#[derive(Debug, PartialEq)]
+pub enum RunError {
+    Undetermined,
+    /// The 1-based number of an instruction whose operands are out of range
+    /// or whose result overflows.
+    Invalid(usize),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::Undetermined => write!(f, "Samples do not determine the opcodes"),
+            RunError::Invalid(n) => write!(f, "Instruction {} of the test program is invalid", n),
+        }
+    }
+}
+
+impl error::Error for RunError {}
+
+/// Register 0 after running the test program with the opcodes figured out
+/// from the samples.
+pub fn part2(samples: &[Sample], instructions: &[Instruction]) -> Result<u32, RunError> {
+    let figured = figure_out(samples).ok_or(RunError::Undetermined)?;
+    let mut mem_state: MemState = [0; 4];
+    for (ix, instr) in instructions.iter().enumerate() {
+        let op = figured[instr.op_number as usize];
+        mem_state =
+            exec(op, instr.a, instr.b, instr.c, mem_state).ok_or(RunError::Invalid(ix + 1))?;
+    }
+    Ok(mem_state[0])
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = (Vec<Sample>, Vec<Instruction>);
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc18_common::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1((samples, _): &Self::Input) -> aoc18_common::Result<usize> {
+        Ok(part1(samples))
+    }
+
+    fn part2((samples, instructions): &Self::Input) -> aoc18_common::Result<u32> {
+        Ok(part2(samples, instructions)?)
+    }
+}
+
+/// The largest register value the generated test program may produce, so
+/// that it cannot overflow.
+const MAX_GENERATED_VALUE: u32 = 1_000_000;
+
+fn random_operand(rng: &mut Rng, is_reg: bool) -> u32 {
+    if is_reg {
+        rng.below(4) as u32
+    } else {
+        rng.below(16) as u32
+    }
+}
+
+impl Generator for Day16 {
+    /// At least `size` samples and a test program of `size` instructions,
+    /// for a random assignment of opcode numbers. The first 16 samples cover
+    /// all opcode numbers, and more are added until the samples determine
+    /// the opcodes. Samples only use 0 to 3 as operands, like the real input.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let mut opcodes = ALL_OPCODES.to_vec();
+        rng.shuffle(&mut opcodes);
+        let mut samples = Vec::new();
+        while samples.len() < size.max(16) || figure_out(&samples).is_none() {
+            let ix = samples.len();
+            let op_number = if ix < 16 { ix } else { rng.below(16) };
+            let before = [0; 4].map(|_: u32| rng.below(4) as u32);
+            let (a, b, c) = (rng.below(4) as u32, rng.below(4) as u32, rng.below(4) as u32);
+            let sample = Sample {
+                before,
+                after: exec(opcodes[op_number], a, b, c, before).unwrap(),
+                op_number: op_number as u32,
+                a,
+                b,
+                c,
+            };
+            samples.push(sample);
+        }
+        let mut text = String::new();
+        for sample in &samples {
+            text += &format!("{}\n\n", sample);
+        }
+        text += "\n\n";
+        let mut state: MemState = [0; 4];
+        for _ in 0..size {
+            let instr = loop {
+                let op_number = rng.below(16);
+                let op = opcodes[op_number];
+                let a = random_operand(rng, op.is_a_reg());
+                let b = random_operand(rng, op.is_b_reg());
+                let c = rng.below(4) as u32;
+                let after = exec(op, a, b, c, state)
+                    .filter(|after| after.iter().all(|&value| value <= MAX_GENERATED_VALUE));
+                if let Some(after) = after {
+                    state = after;
+                    break Instruction {
+                        op_number: op_number as u32,
+                        a,
+                        b,
+                        c,
+                    };
+                }
+            };
+            text += &format!("{}\n", instr);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Before: [3, 2, 1, 1]
+9 2 1 2
+After:  [3, 2, 2, 1]
+
+
+
+9 2 1 2";
+
+    #[test]
+    fn example() {
+        let (samples, instructions) = parse(EXAMPLE).unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(instructions.len(), 1);
+        let mut matches = sample_matches(&samples[0]);
+        matches.sort_by_key(|op| format!("{:?}", op));
+        assert_eq!(matches, vec![Opcode::Addi, Opcode::Mulr, Opcode::Seti]);
+        assert_eq!(part1(&samples), 1);
+    }
+
+    #[test]
+    fn exec_opcodes() {
+        let state = [3, 2, 1, 1];
+        assert_eq!(exec(Opcode::Addr, 0, 1, 3, state), Some([3, 2, 1, 5]));
+        assert_eq!(exec(Opcode::Muli, 0, 7, 3, state), Some([3, 2, 1, 21]));
+        assert_eq!(exec(Opcode::Banr, 0, 2, 3, state), Some([3, 2, 1, 1]));
+        assert_eq!(exec(Opcode::Bori, 1, 4, 3, state), Some([3, 2, 1, 6]));
+        assert_eq!(exec(Opcode::Gtir, 5, 0, 3, state), Some([3, 2, 1, 1]));
+        assert_eq!(exec(Opcode::Eqrr, 2, 3, 0, state), Some([1, 2, 1, 1]));
+        assert_eq!(exec(Opcode::Addr, 0, 4, 3, state), None);
+        assert_eq!(exec(Opcode::Seti, 7, 0, 4, state), None);
+        assert_eq!(exec(Opcode::Mulr, 0, 0, 0, [u32::MAX, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn operands_out_of_range() {
+        let input = "Before: [3, 2, 1, 1]\n9 5 1 2\nAfter:  [3, 2, 5, 1]\n\n\n\n9 2 1 2\n";
+        let (samples, _) = parse(input).unwrap();
+        assert_eq!(sample_matches(&samples[0]), vec![Opcode::Seti]);
+    }
+
+    #[test]
+    fn invalid_program() {
+        let input = Day16::generate(&mut Rng::new(1), 100);
+        let (samples, mut instructions) = parse(&input).unwrap();
+        let figured = figure_out(&samples).unwrap();
+        // Every opcode but `seti` reads register `a` or `b`.
+        let op_number = figured.iter().position(|&op| op != Opcode::Seti).unwrap() as u32;
+        instructions.truncate(2);
+        instructions.push(Instruction {
+            op_number,
+            a: 5,
+            b: 5,
+            c: 0,
+        });
+        assert_eq!(part2(&samples, &instructions), Err(RunError::Invalid(3)));
+        assert_eq!(part2(&samples[..1], &instructions), Err(RunError::Undetermined));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("Before: [3, 2, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n\n\n\n")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.reason), (1, Error::Memory));
+        assert_eq!(parse("Before: [3, 2, 1, 1]").err().unwrap().reason, Error::NoProgram);
+    }
+
+    #[test]
+    fn generated_input() {
+        let input = Day16::generate(&mut Rng::new(1), 500);
+        let (samples, instructions) = parse(&input).unwrap();
+        assert_eq!(instructions.len(), 500);
+        assert!(samples.len() >= 500);
+        assert!(samples.iter().all(|sample| !sample_matches(sample).is_empty()));
+        assert!(part2(&samples, &instructions).is_ok());
+    }
+
+    #[test]
+    fn small_generated_inputs() {
+        for &size in &[1, 16, 50] {
+            for seed in 0..10 {
+                let input = Day16::generate(&mut Rng::new(seed), size);
+                let (samples, instructions) = parse(&input).unwrap();
+                assert!(samples.len() >= size.max(16));
+                assert!(part2(&samples, &instructions).is_ok());
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use solution::Answer;
+
+/// The known answers to a day's two parts, as stored in its `answers` file.
+///
+/// The file uses the same format as the day's own output, so it can be
+/// created with `cargo run --release > answers`:
+///
+/// ```text
+/// Part 1: 424112
+/// Part 2: 3487352628
+/// ```
+///
+/// An answer continues on the following lines until the next `Part N:`,
+/// which is how multi-line answers such as rendered pictures are stored.
+/// Parts that are not in the file are missing.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    parts: [Option<String>; 2],
+}
+
+impl Expected {
+    pub fn parse(text: &str) -> Result<Expected, String> {
+        let mut expected = Expected::default();
+        let mut current: Option<usize> = None;
+        for (ix, line) in text.lines().enumerate() {
+            if let Some((part, answer)) = part_header(line) {
+                if expected.parts[part].is_some() {
+                    return Err(format!("line {}: Part {} is given twice", ix + 1, part + 1));
+                }
+                expected.parts[part] = Some(answer.to_string());
+                current = Some(part);
+            } else if let Some(part) = current {
+                let answer = expected.parts[part].as_mut().unwrap();
+                answer.push('\n');
+                answer.push_str(line);
+            } else if !line.trim().is_empty() {
+                return Err(format!("line {}: Expected \"Part 1: <answer>\"", ix + 1));
+            }
+        }
+        for answer in expected.parts.iter_mut().flatten() {
+            // Multi-line answers start on the line after the header.
+            if answer.starts_with('\n') {
+                answer.remove(0);
+            }
+            let len = answer.trim_end().len();
+            answer.truncate(len);
+        }
+        Ok(expected)
+    }
+
+    /// The expected answer to `part`, which is 1 or 2.
+    pub fn part(&self, part: usize) -> Option<&str> {
+        self.parts[part - 1].as_deref()
+    }
+
+    /// Compares the answers that were actually produced with these.
+    pub fn check(&self, answers: &[Answer; 2]) -> [Check; 2] {
+        [
+            Check::new(self.part(1), &answers[0]),
+            Check::new(self.part(2), &answers[1]),
+        ]
+    }
+}
+
+fn part_header(line: &str) -> Option<(usize, &str)> {
+    let rest = line.strip_prefix("Part ")?;
+    let (part, answer) = rest.split_at(rest.find(':')?);
+    let part = match part {
+        "1" => 0,
+        "2" => 1,
+        _ => return None,
+    };
+    Some((part, answer[1..].trim()))
+}
+
+/// The outcome of checking one part against its expected answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String, actual: Answer },
+    Missing,
+}
+
+impl Check {
+    fn new(expected: Option<&str>, actual: &Answer) -> Check {
+        match (expected, actual) {
+            (None, _) => Check::Missing,
+            (Some(expected), Answer::Solved(answer)) if expected == answer => Check::Pass,
+            (Some(expected), actual) => Check::Fail {
+                expected: expected.to_string(),
+                actual: actual.clone(),
+            },
+        }
+    }
+}
+
+/// Shows a failed check as a line by line diff, with `-` marking expected
+/// lines and `+` the lines that were produced instead.
+pub struct Diff<'a> {
+    pub expected: &'a str,
+    pub actual: &'a Answer,
+}
+
+impl<'a> fmt::Display for Diff<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let actual = match self.actual {
+            Answer::Solved(answer) => answer.clone(),
+            other => other.to_string(),
+        };
+        let expected: Vec<_> = self.expected.lines().collect();
+        let actual: Vec<_> = actual.lines().collect();
+        for ix in 0..expected.len().max(actual.len()) {
+            match (expected.get(ix), actual.get(ix)) {
+                (Some(e), Some(a)) if e == a => writeln!(f, "    {}", e)?,
+                (e, a) => {
+                    if let Some(e) = e {
+                        writeln!(f, "  - {}", e)?;
+                    }
+                    if let Some(a) = a {
+                        writeln!(f, "  + {}", a)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single_and_multi_line() {
+        let expected = Expected::parse("Part 1: \n#..#\n####\n\nPart 2: 10081\n").unwrap();
+        assert_eq!(expected.part(1), Some("#..#\n####"));
+        assert_eq!(expected.part(2), Some("10081"));
+        let expected = Expected::parse("Part 1: 424112\n").unwrap();
+        assert_eq!(expected.part(2), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Expected::parse("424112").is_err());
+        assert!(Expected::parse("Part 1: 1\nPart 1: 2").is_err());
+    }
+
+    #[test]
+    fn check() {
+        let expected = Expected::parse("Part 1: 439").unwrap();
+        let answers = [Answer::Solved("438".to_string()), Answer::Unsolved];
+        assert_eq!(
+            expected.check(&answers),
+            [
+                Check::Fail {
+                    expected: "439".to_string(),
+                    actual: Answer::Solved("438".to_string()),
+                },
+                Check::Missing,
+            ]
+        );
+        let diff = Diff {
+            expected: "439",
+            actual: &answers[0],
+        };
+        assert_eq!(diff.to_string(), "  - 439\n  + 438\n");
+    }
+}
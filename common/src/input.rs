@@ -0,0 +1,204 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Interprets a command line argument: `-` means stdin, anything else
+    /// is a path, and no argument at all means the file `input`.
+    pub fn from_arg(arg: Option<&str>) -> Source {
+        match arg {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+            None => Source::File(PathBuf::from("input")),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Failure to read an input from its source.
+#[derive(Debug)]
+pub struct LoadError {
+    pub source: Source,
+    pub err: io::Error,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not read {}: {}", self.source, self.err)
+    }
+}
+
+impl Error for LoadError {}
+
+/// A line of the input that could not be parsed.
+///
+/// `line` is 1-based, so it can be looked up directly in an editor. `text`
+/// is the offending line, or the offending part of it for inputs that
+/// consist of a single long line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError<E> {
+    pub line: usize,
+    pub text: String,
+    pub reason: E,
+}
+
+impl<E> ParseError<E> {
+    /// An error for the line with 0-based index `ix`.
+    pub fn at(ix: usize, text: &str, reason: E) -> Self {
+        ParseError {
+            line: ix + 1,
+            text: text.to_string(),
+            reason,
+        }
+    }
+}
+
+const MAX_REPORTED_CHARS: usize = 60;
+
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}: ", self.line, self.reason)?;
+        if self.text.chars().count() > MAX_REPORTED_CHARS {
+            let start: String = self.text.chars().take(MAX_REPORTED_CHARS).collect();
+            write!(f, "{:?}...", start)
+        } else {
+            write!(f, "{:?}", self.text)
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for ParseError<E> {}
+
+/// The full text of a puzzle input.
+pub struct Input {
+    source: Source,
+    text: String,
+}
+
+impl Input {
+    pub fn load(source: Source) -> Result<Input, LoadError> {
+        let mut text = String::new();
+        let result = match source {
+            Source::Stdin => io::stdin().read_to_string(&mut text),
+            Source::File(ref path) => File::open(path).and_then(|mut f| f.read_to_string(&mut text)),
+        };
+        match result {
+            Ok(_) => Ok(Input { source, text }),
+            Err(err) => Err(LoadError { source, err }),
+        }
+    }
+
+    /// Loads the input named by the first command line argument, exiting
+    /// with an error report if it cannot be read.
+    pub fn from_args() -> Input {
+        let arg = env::args().nth(1);
+        Input::load(Source::from_arg(arg.as_deref())).or_exit()
+    }
+
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> Vec<&str> {
+        self.text.lines().collect()
+    }
+
+    /// Parses an input that consists of a single line as a `T`.
+    pub fn record<T: FromStr>(&self) -> Result<T, ParseError<T::Err>> {
+        parse_record(&self.text)
+    }
+
+    /// Parses every line as a `T`.
+    pub fn records<T: FromStr>(&self) -> Result<Vec<T>, ParseError<T::Err>> {
+        parse_records(&self.text)
+    }
+}
+
+/// Parses a text that consists of a single line as a `T`.
+pub fn parse_record<T: FromStr>(text: &str) -> Result<T, ParseError<T::Err>> {
+    let line = text.trim();
+    line.parse().map_err(|reason| ParseError::at(0, line, reason))
+}
+
+/// Parses every line of a text as a `T`.
+pub fn parse_records<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError<T::Err>> {
+    parse_lines(text.lines().enumerate())
+}
+
+/// Parses numbered lines, such as a slice of `Input::lines` zipped with
+/// their original 0-based indices, reporting the first failure.
+pub fn parse_lines<'a, T, I>(lines: I) -> Result<Vec<T>, ParseError<T::Err>>
+where
+    T: FromStr,
+    I: IntoIterator<Item = (usize, &'a str)>,
+{
+    lines
+        .into_iter()
+        .map(|(ix, line)| line.parse().map_err(|reason| ParseError::at(ix, line, reason)))
+        .collect()
+}
+
+/// Prints an error report and exits.
+pub fn fail<E: fmt::Display>(err: E) -> ! {
+    eprintln!("error: {}", err);
+    process::exit(1);
+}
+
+/// Unwrapping that reports the error through `fail` instead of panicking.
+pub trait OrExit<T> {
+    fn or_exit(self) -> T;
+}
+
+impl<T, E: fmt::Display> OrExit<T> for Result<T, E> {
+    fn or_exit(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(err) => fail(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_records_reports_line() {
+        let err = parse_records::<i64>("+1\n-2\nthree\n+1").unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (3, "three"));
+        assert!(err.to_string().starts_with("line 3: "));
+    }
+
+    #[test]
+    fn long_lines_are_elided() {
+        let text = "1 ".repeat(100);
+        let err = ParseError::at(0, &text, "Bad number");
+        let shown = err.to_string();
+        assert!(shown.ends_with("..."));
+        assert!(shown.len() < 100);
+    }
+}
@@ -0,0 +1,18 @@
+//! Code shared between the days: reading puzzle inputs, reporting errors,
+//! the `Solution` and `Generator` traits that every day implements and
+//! checking answers against the known ones.
+
+mod answers;
+mod generate;
+mod input;
+mod solution;
+
+pub use answers::{Check, Diff, Expected};
+pub use generate::{Generator, Rng};
+
+pub use input::{
+    fail, parse_lines, parse_record, parse_records, Input, LoadError, OrExit, ParseError, Source,
+};
+pub use solution::{
+    print_answers, run, solve, solve_timed, Answer, Result, Solution, Timings, Unsolved,
+};
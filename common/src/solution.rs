@@ -0,0 +1,112 @@
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use input::{fail, Input};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// A day's puzzle, split into parsing the input and solving the two parts.
+pub trait Solution {
+    type Input;
+    type Part1: fmt::Display;
+    type Part2: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2>;
+}
+
+/// The error of a part that has not been solved yet.
+#[derive(Debug)]
+pub struct Unsolved;
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Not solved yet")
+    }
+}
+
+impl Error for Unsolved {}
+
+/// What solving one part produced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    Unsolved,
+    Failed(String),
+}
+
+impl Answer {
+    fn from_result<T: fmt::Display>(result: Result<T>) -> Answer {
+        match result {
+            Ok(answer) => Answer::Solved(answer.to_string()),
+            Err(ref err) if err.is::<Unsolved>() => Answer::Unsolved,
+            Err(err) => Answer::Failed(err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // Multi-line answers, such as rendered text, start on a line
+            // of their own.
+            Answer::Solved(answer) if answer.contains('\n') => write!(f, "\n{}", answer),
+            Answer::Solved(answer) => write!(f, "{}", answer),
+            Answer::Unsolved => write!(f, "(not solved yet)"),
+            Answer::Failed(err) => write!(f, "(error: {})", err),
+        }
+    }
+}
+
+/// How long each stage of solving a day took.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/// Parses `text` and solves both parts of `S`.
+pub fn solve<S: Solution>(text: &str) -> Result<[Answer; 2]> {
+    solve_timed::<S>(text).map(|(answers, _)| answers)
+}
+
+/// Like `solve`, but also measures parsing and each part separately.
+pub fn solve_timed<S: Solution>(text: &str) -> Result<([Answer; 2], Timings)> {
+    let start = Instant::now();
+    let input = S::parse(text)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = Answer::from_result(S::part1(&input));
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = Answer::from_result(S::part2(&input));
+    let part2_time = start.elapsed();
+
+    let timings = Timings {
+        parse,
+        part1: part1_time,
+        part2: part2_time,
+    };
+    Ok(([part1, part2], timings))
+}
+
+pub fn print_answers(answers: &[Answer; 2]) {
+    for (part, answer) in (1..).zip(answers.iter()) {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+/// Entry point for a day's own binary: solves the input named on the
+/// command line and prints the answers.
+pub fn run<S: Solution>() {
+    let input = Input::from_args();
+    match solve::<S>(input.text()) {
+        Ok(answers) => print_answers(&answers),
+        Err(err) => fail(err),
+    }
+}
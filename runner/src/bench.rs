@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::time::Duration;
+
+use aoc18_common::Timings;
+
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// The spread of a stage's running times over all repetitions.
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn of(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Benchmark results for one day, one entry per stage in `STAGES`.
+pub struct DayBench {
+    pub day: u32,
+    pub stages: [Stats; 3],
+}
+
+impl DayBench {
+    /// Summarizes the timings of at least one repetition.
+    pub fn new(day: u32, timings: &[Timings]) -> DayBench {
+        let stage = |f: fn(&Timings) -> Duration| Stats::of(timings.iter().map(f).collect());
+        DayBench {
+            day,
+            stages: [
+                stage(|t| t.parse),
+                stage(|t| t.part1),
+                stage(|t| t.part2),
+            ],
+        }
+    }
+}
+
+/// Median times from an earlier run, keyed by day and stage.
+pub type Saved = HashMap<(u32, String), Duration>;
+
+/// Writes one line per day and stage: the day, the stage and the minimum,
+/// median and maximum in nanoseconds.
+pub fn save(path: &str, benches: &[DayBench]) -> Result<(), String> {
+    let mut text = String::from("# day stage min_ns median_ns max_ns\n");
+    for bench in benches {
+        for (stage, stats) in STAGES.iter().zip(bench.stages.iter()) {
+            writeln!(
+                text,
+                "{:02} {} {} {} {}",
+                bench.day,
+                stage,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos()
+            )
+            .unwrap();
+        }
+    }
+    fs::write(path, text).map_err(|err| format!("Could not write {}: {}", path, err))
+}
+
+pub fn load(path: &str) -> Result<Saved, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path, err))?;
+    let mut saved = Saved::new();
+    for (ix, line) in text.lines().enumerate() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let bad_line = || format!("{}: line {}: Bad benchmark result: {:?}", path, ix + 1, line);
+        let fields: Vec<_> = line.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(bad_line());
+        }
+        let day = fields[0].parse().map_err(|_| bad_line())?;
+        let median = fields[3].parse().map_err(|_| bad_line())?;
+        saved.insert((day, fields[1].to_string()), Duration::from_nanos(median));
+    }
+    Ok(saved)
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Prints a day's results, comparing the medians with `saved` if given.
+pub fn report(bench: &DayBench, saved: Option<&Saved>) {
+    for (stage, stats) in STAGES.iter().zip(bench.stages.iter()) {
+        print!(
+            "Day {:02} {:<5}  min {:>10.3} ms  median {:>10.3} ms  max {:>10.3} ms",
+            bench.day,
+            stage,
+            millis(stats.min),
+            millis(stats.median),
+            millis(stats.max)
+        );
+        if let Some(saved) = saved {
+            match saved.get(&(bench.day, stage.to_string())) {
+                Some(&before) if before > Duration::from_secs(0) => {
+                    let change = (millis(stats.median) / millis(before) - 1.0) * 100.0;
+                    print!("  ({:+.1}% vs {:.3} ms)", change, millis(before));
+                }
+                Some(_) => print!("  (was 0 ms)"),
+                None => print!("  (new)"),
+            }
+        }
+        println!();
+    }
+}
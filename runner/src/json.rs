@@ -0,0 +1,75 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc18_common::{Answer, Timings};
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A record for one part, such as
+/// `{"day": 1, "part": 2, "answer": "124645", "elapsed": 0.011}`.
+///
+/// `answer` is null for parts that are not solved yet or that failed, with
+/// the failure in an extra `error` field. `elapsed` is the time taken by the
+/// part in seconds, not counting parsing.
+pub fn record(day: u32, part: usize, answer: &Answer, elapsed: Duration) -> String {
+    let (answer, error) = match answer {
+        Answer::Solved(answer) => (quote(answer), None),
+        Answer::Unsolved => ("null".to_string(), None),
+        Answer::Failed(err) => ("null".to_string(), Some(quote(err))),
+    };
+    let mut record = format!(
+        "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"elapsed\": {}",
+        day,
+        part,
+        answer,
+        elapsed.as_secs_f64()
+    );
+    if let Some(error) = error {
+        write!(record, ", \"error\": {}", error).unwrap();
+    }
+    record.push('}');
+    record
+}
+
+pub fn print_records(day: u32, answers: &[Answer; 2], timings: &Timings) {
+    println!("{}", record(day, 1, &answers[0], timings.part1));
+    println!("{}", record(day, 2, &answers[1], timings.part2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let answer = Answer::Solved("#.\n.#".to_string());
+        assert_eq!(
+            record(10, 1, &answer, Duration::from_millis(1500)),
+            r##"{"day": 10, "part": 1, "answer": "#.\n.#", "elapsed": 1.5}"##
+        );
+        assert_eq!(
+            record(9, 2, &Answer::Unsolved, Duration::from_secs(0)),
+            r#"{"day": 9, "part": 2, "answer": null, "elapsed": 0}"#
+        );
+        let failed = Answer::Failed("No \"guards\"".to_string());
+        assert_eq!(
+            record(4, 1, &failed, Duration::from_secs(2)),
+            r#"{"day": 4, "part": 1, "answer": null, "elapsed": 2, "error": "No \"guards\""}"#
+        );
+    }
+}
@@ -0,0 +1,335 @@
+extern crate aoc18_01;
+extern crate aoc18_02;
+extern crate aoc18_03;
+extern crate aoc18_04;
+extern crate aoc18_05;
+extern crate aoc18_06;
+extern crate aoc18_07;
+extern crate aoc18_08;
+extern crate aoc18_09;
+extern crate aoc18_10;
+extern crate aoc18_11;
+extern crate aoc18_12;
+extern crate aoc18_14;
+extern crate aoc18_16;
+extern crate aoc18_common;
+
+mod bench;
+mod json;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+
+use aoc18_01::Day01;
+use aoc18_02::Day02;
+use aoc18_03::Day03;
+use aoc18_04::Day04;
+use aoc18_05::Day05;
+use aoc18_06::Day06;
+use aoc18_07::Day07;
+use aoc18_08::Day08;
+use aoc18_09::Day09;
+use aoc18_10::Day10;
+use aoc18_11::Day11;
+use aoc18_12::Day12;
+use aoc18_14::Day14;
+use aoc18_16::Day16;
+use aoc18_common::{
+    print_answers, solve_timed, Answer, Check, Diff, Expected, Generator, Input, Rng, Solution,
+    Source, Timings,
+};
+
+use bench::DayBench;
+
+const USAGE: &str = "\
+Usage: aoc run <days> [input] [--format text|json]
+       aoc verify [days]
+       aoc bench <days> [--reps N] [--save FILE] [--compare FILE]
+       aoc gen <day> <size> [seed]
+
+<days> is a single day (7), an inclusive range (3-7) or `all`.
+[input] may only be given for a single day, with - meaning stdin; it
+defaults to <dd>/input. --format json prints one JSON object per line
+and part, with the fields day, part, answer and elapsed (in seconds).
+
+verify solves each day's <dd>/input and compares the answers with the
+ones stored in <dd>/answers. [days] defaults to `all`.
+
+bench solves each day's <dd>/input N times (default 5) and reports the
+minimum, median and maximum time of parsing and of each part. --save
+writes the results to FILE and --compare compares the medians with a FILE
+saved earlier.
+
+gen prints a random input for a day, of about <size> records, from
+[seed] (default 0). The same seed always gives the same input.";
+
+type Solver = fn(&str) -> aoc18_common::Result<([Answer; 2], Timings)>;
+
+#[derive(Clone, Copy)]
+struct Day {
+    number: u32,
+    solve: Solver,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+const fn day<S: Solution + Generator>(number: u32) -> Day {
+    Day {
+        number,
+        solve: solve_timed::<S>,
+        generate: S::generate,
+    }
+}
+
+const DAYS: &[Day] = &[
+    day::<Day01>(1),
+    day::<Day02>(2),
+    day::<Day03>(3),
+    day::<Day04>(4),
+    day::<Day05>(5),
+    day::<Day06>(6),
+    day::<Day07>(7),
+    day::<Day08>(8),
+    day::<Day09>(9),
+    day::<Day10>(10),
+    day::<Day11>(11),
+    day::<Day12>(12),
+    day::<Day14>(14),
+    day::<Day16>(16),
+];
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    s.parse::<u32>()
+        .map_err(|_| format!("Invalid day: {:?}", s))
+}
+
+fn parse_days(spec: &str) -> Result<Vec<Day>, String> {
+    if spec == "all" {
+        return Ok(DAYS.to_vec());
+    }
+    let (first, last) = match spec.find('-') {
+        Some(ix) => (parse_day(&spec[..ix])?, parse_day(&spec[ix + 1..])?),
+        None => {
+            let day = parse_day(spec)?;
+            (day, day)
+        }
+    };
+    if first > last {
+        return Err(format!("Empty range of days: {}", spec));
+    }
+    let days: Vec<_> = DAYS
+        .iter()
+        .copied()
+        .filter(|day| (first..=last).contains(&day.number))
+        .collect();
+    if first == last && days.is_empty() {
+        return Err(format!("Day {} is not solved", first));
+    }
+    Ok(days)
+}
+
+fn default_input(day: u32) -> Source {
+    Source::File(PathBuf::from(format!("{:02}/input", day)))
+}
+
+fn load_expected(day: u32) -> Result<Expected, String> {
+    let path = PathBuf::from(format!("{:02}/answers", day));
+    match fs::read_to_string(&path) {
+        Ok(text) => Expected::parse(&text).map_err(|err| format!("{}: {}", path.display(), err)),
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(Expected::default()),
+        Err(err) => Err(format!("Could not read {}: {}", path.display(), err)),
+    }
+}
+
+/// Solves a day's default input, turning a failure to load or parse it into
+/// failed answers so that verification can carry on with the other days.
+fn solve_default(day: Day) -> [Answer; 2] {
+    let result = Input::load(default_input(day.number))
+        .map_err(|err| err.to_string())
+        .and_then(|input| (day.solve)(input.text()).map_err(|err| err.to_string()));
+    match result {
+        Ok((answers, _)) => answers,
+        Err(err) => [Answer::Failed(err.clone()), Answer::Failed(err)],
+    }
+}
+
+fn verify(args: &[String]) -> Result<(), String> {
+    if args.len() > 1 {
+        return Err(USAGE.to_string());
+    }
+    let days = parse_days(args.first().map_or("all", |arg| arg.as_str()))?;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in days {
+        let expected = load_expected(day.number)?;
+        let answers = solve_default(day);
+        for (part, check) in (1..).zip(expected.check(&answers).iter()) {
+            print!("Day {:02} part {}: ", day.number, part);
+            match check {
+                Check::Pass => {
+                    passed += 1;
+                    println!("pass");
+                }
+                Check::Fail { expected, actual } => {
+                    failed += 1;
+                    println!("FAIL");
+                    print!("{}", Diff { expected, actual });
+                }
+                Check::Missing => {
+                    missing += 1;
+                    println!("missing, got {}", answers[part - 1]);
+                }
+            }
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        return Err("Verification failed".to_string());
+    }
+    Ok(())
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let days = parse_days(args.first().ok_or("No days given")?)?;
+    let mut reps = 5;
+    let mut save = None;
+    let mut compare = None;
+    let mut flags = args[1..].iter();
+    while let Some(flag) = flags.next() {
+        let value = flags
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", flag))?;
+        match flag.as_str() {
+            "--reps" => {
+                reps = value
+                    .parse()
+                    .ok()
+                    .filter(|&reps| reps > 0)
+                    .ok_or_else(|| format!("Invalid number of repetitions: {:?}", value))?
+            }
+            "--save" => save = Some(value),
+            "--compare" => compare = Some(value),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let saved = match compare {
+        Some(path) => Some(bench::load(path)?),
+        None => None,
+    };
+
+    let mut benches = Vec::new();
+    for Day { number: day, solve, .. } in days {
+        let input = Input::load(default_input(day)).map_err(|err| err.to_string())?;
+        let timings = (0..reps)
+            .map(|_| solve(input.text()).map(|(_, timings)| timings))
+            .collect::<aoc18_common::Result<Vec<_>>>()
+            .map_err(|err| format!("Day {:02}: {}", day, err))?;
+        let bench = DayBench::new(day, &timings);
+        bench::report(&bench, saved.as_ref());
+        benches.push(bench);
+    }
+    if let Some(path) = save {
+        bench::save(path, &benches)?;
+    }
+    Ok(())
+}
+
+fn gen(args: &[String]) -> Result<(), String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(USAGE.to_string());
+    }
+    let days = parse_days(&args[0])?;
+    if days.len() != 1 {
+        return Err("Inputs can only be generated for a single day".to_string());
+    }
+    let size = args[1]
+        .parse()
+        .map_err(|_| format!("Invalid size: {:?}", args[1]))?;
+    let seed = match args.get(2) {
+        Some(seed) => seed
+            .parse()
+            .map_err(|_| format!("Invalid seed: {:?}", seed))?,
+        None => 0,
+    };
+    print!("{}", (days[0].generate)(&mut Rng::new(seed), size));
+    Ok(())
+}
+
+enum Format {
+    Text,
+    Json,
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let mut format = Format::Text;
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            format = match args.next().map(|value| value.as_str()) {
+                Some("text") => Format::Text,
+                Some("json") => Format::Json,
+                _ => return Err("--format must be text or json".to_string()),
+            };
+        } else {
+            positional.push(arg);
+        }
+    }
+    if positional.len() > 2 {
+        return Err(USAGE.to_string());
+    }
+    let days = parse_days(positional.first().ok_or("No days given")?)?;
+    let source = positional.get(1).map(|arg| Source::from_arg(Some(arg)));
+    if source.is_some() && days.len() != 1 {
+        return Err("An input file can only be given for a single day".to_string());
+    }
+    for Day { number: day, solve, .. } in days {
+        let source = source.clone().unwrap_or_else(|| default_input(day));
+        let input = Input::load(source).map_err(|err| err.to_string())?;
+        let (answers, timings) =
+            solve(input.text()).map_err(|err| format!("Day {:02}: {}", day, err))?;
+        match format {
+            Format::Text => {
+                println!("Day {:02}", day);
+                print_answers(&answers);
+            }
+            Format::Json => json::print_records(day, &answers, &timings),
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("gen") => gen(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(spec: &str) -> Result<Vec<u32>, String> {
+        parse_days(spec).map(|days| days.iter().map(|day| day.number).collect())
+    }
+
+    #[test]
+    fn day_specs() {
+        assert_eq!(days("7"), Ok(vec![7]));
+        assert_eq!(days("11-16"), Ok(vec![11, 12, 14, 16]));
+        assert_eq!(days("all").unwrap().len(), DAYS.len());
+        assert!(days("13").is_err());
+        assert!(days("7-3").is_err());
+        assert!(days("x").is_err());
+    }
+}